@@ -59,6 +59,7 @@ impl ChamaOptics {
             export_config: &crate::export_config::ExportConfig,
         ) {
             let new_path = pi.bulk_path(export_config);
+            pi.sequence = idx + 1;
 
             match export_config
                 .theme_reg
@@ -94,10 +95,66 @@ impl ChamaOptics {
         }
     }
 
+    fn save_contact_sheet(&mut self) {
+        if !self.export_config.output_name.check_folder_available(true) {
+            log::error!(
+                "Cannot access following directory {:?}",
+                self.export_config.output_name.folder
+            );
+            return;
+        }
+
+        let images = self
+            .packed_images
+            .iter()
+            .filter(|pi| pi.on_contact_sheet)
+            .collect::<Vec<_>>();
+        if images.is_empty() {
+            return;
+        }
+
+        match self
+            .export_config
+            .contact_sheet
+            .save(&images, &self.export_config)
+        {
+            Ok(paths) => {
+                log::info!("Saved contact sheet to {paths:?}");
+            }
+            Err(e) => {
+                log::error!("Failed to save contact sheet: {e:?}");
+            }
+        }
+    }
+
+    fn save_photo_booth(&mut self) {
+        if !self.export_config.output_name.check_folder_available(true) {
+            log::error!(
+                "Cannot access following directory {:?}",
+                self.export_config.output_name.folder
+            );
+            return;
+        }
+
+        match self
+            .export_config
+            .photo_booth
+            .save(&self.packed_images, &self.export_config)
+        {
+            Ok(path) => {
+                log::info!("Saved photo booth strip to {path:?}");
+            }
+            Err(e) => {
+                log::error!("Failed to save photo booth strip: {e:?}");
+            }
+        }
+    }
+
     fn update_packed_image(&mut self, ui: &mut egui::Ui) {
         let mut remove_index: Option<usize> = None;
 
         for (idx, pi) in self.packed_images.iter_mut().enumerate() {
+            pi.sequence = idx + 1;
             match pi.update_ui(ui, &self.export_config) {
                 crate::packed_image::PackedImageEvent::None => { /* Nothing */ }
                 crate::packed_image::PackedImageEvent::Remove => {
@@ -179,12 +236,25 @@ impl eframe::App for ChamaOptics {
                     self.save_packed_image_all(ui);
                 }
 
+                if ui.button(t!("app.images.save_contact_sheet")).clicked() {
+                    self.save_contact_sheet();
+                }
+
                 if ui.button(t!("app.images.remove_all")).clicked() {
                     // need Arc<RwLock<T>> later
                     self.packed_images.clear();
                 }
             });
 
+            if self
+                .export_config
+                .photo_booth
+                .update_ui(ui, &self.packed_images)
+                == crate::export_config::photo_booth::PhotoBoothEvent::Save
+            {
+                self.save_photo_booth();
+            }
+
             // Scrollable stuff
             egui::ScrollArea::vertical()
                 .auto_shrink([false; 2])
@@ -0,0 +1,143 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Film grain from value noise on lattice sized relative to short edge of image,
+//! so same photo gets same grain pattern on any output resolution.
+//! Noise comes from hash of seed and lattice position, no random state,
+//! so same seed always gives byte-identical output.
+
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+
+/// Grain size range in per mille of short edge
+const MIN_SIZE: f32 = 0.25;
+const MAX_SIZE: f32 = 2.5;
+/// Deviation of grain on amount 100, in sRGB encoded 0.0 ..= 1.0
+const MAX_STRENGTH: f32 = 0.25;
+
+/// Stable 64 bit hash for seed, `DefaultHasher` can change between Rust versions
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, x| {
+        (hash ^ *x as u64).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+/// -1.0 ..= 1.0 on lattice position
+fn hash(seed: u64, x: i64, y: i64) -> f32 {
+    // splitmix64 finalizer over mixed position
+    let mut h = seed
+        ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    h ^= h >> 30;
+    h = h.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    h ^= h >> 27;
+    h = h.wrapping_mul(0x94D0_49BB_1331_11EB);
+    h ^= h >> 31;
+    (h >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+}
+
+/// Smoothly interpolated lattice noise at position in lattice unit
+fn value_noise(seed: u64, x: f32, y: f32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (sx, sy) = (fx * fx * (3.0 - 2.0 * fx), fy * fy * (3.0 - 2.0 * fy));
+    let (ix, iy) = (x0 as i64, y0 as i64);
+
+    let top = hash(seed, ix, iy) + (hash(seed, ix + 1, iy) - hash(seed, ix, iy)) * sx;
+    let bottom =
+        hash(seed, ix, iy + 1) + (hash(seed, ix + 1, iy + 1) - hash(seed, ix, iy + 1)) * sx;
+    top + (bottom - top) * sy
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Grain {
+    /// 0 ~ 100, no grain on 0
+    pub amount: f32,
+    /// 0 ~ 100, fine to coarse
+    pub size: f32,
+    /// 0 ~ 100, smooth clumps to sharp speckles
+    pub roughness: f32,
+    /// 0 ~ 100, luminance only grain to independent grain on each channel
+    pub color: f32,
+}
+
+impl core::default::Default for Grain {
+    fn default() -> Self {
+        Self {
+            amount: 0.0,
+            size: 25.0,
+            roughness: 50.0,
+            color: 0.0,
+        }
+    }
+}
+
+impl Grain {
+    pub fn is_identity(&self) -> bool {
+        self.amount <= 0.0
+    }
+
+    /// Per pixel function on sRGB encoded 0.0 ..= 1.0 for image of `width` x `height`
+    pub(crate) fn curve(
+        &self,
+        width: u32,
+        height: u32,
+        seed: u64,
+    ) -> impl Fn(u32, u32, [f32; 3]) -> [f32; 3] {
+        let short = width.min(height).max(1) as f32;
+        let cell = short / 1000.0
+            * (MIN_SIZE + (MAX_SIZE - MIN_SIZE) * self.size.clamp(0.0, 100.0) / 100.0);
+        // Grain finer than pixel averages out on real downscale, so does its deviation
+        let strength = self.amount.clamp(0.0, 100.0) / 100.0 * MAX_STRENGTH * cell.min(1.0);
+        let rough = self.roughness.clamp(0.0, 100.0) / 100.0;
+        let norm = (1.0 + rough * rough).sqrt();
+        let color = self.color.clamp(0.0, 100.0) / 100.0;
+        let seeds: [u64; 4] = std::array::from_fn(|c| {
+            seed.wrapping_add((c as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        });
+
+        // Second octave on double frequency adds sharp speckles
+        let octaves = move |seed: u64, x: f32, y: f32| {
+            let base = value_noise(seed, x, y);
+            if rough > 0.0 {
+                (base + rough * value_noise(!seed, x * 2.0 + 17.0, y * 2.0 + 31.0)) / norm
+            } else {
+                base
+            }
+        };
+
+        move |x, y, rgb| {
+            let (px, py) = ((x as f32 + 0.5) / cell, (y as f32 + 0.5) / cell);
+            let luma = octaves(seeds[3], px, py);
+            let lightness = super::tone::luminance(rgb);
+            // Grain shows most on midtone like film, least on pure black and white
+            let weight = 0.3 + 2.8 * lightness * (1.0 - lightness);
+
+            std::array::from_fn(|c| {
+                let noise = if color > 0.0 {
+                    luma + (octaves(seeds[c], px, py) - luma) * color
+                } else {
+                    luma
+                };
+                (rgb[c] + noise * strength * weight).clamp(0.0, 1.0)
+            })
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::Slider::new(&mut self.amount, 0.0..=100.0).text(t!("develop.grain.amount")));
+        ui.add_enabled_ui(!self.is_identity(), |ui| {
+            ui.add(egui::Slider::new(&mut self.size, 0.0..=100.0).text(t!("develop.grain.size")));
+            ui.add(
+                egui::Slider::new(&mut self.roughness, 0.0..=100.0)
+                    .text(t!("develop.grain.roughness")),
+            );
+            ui.add(egui::Slider::new(&mut self.color, 0.0..=100.0).text(t!("develop.grain.color")))
+                .on_hover_text(t!("develop.grain.color_description"));
+        });
+    }
+}
@@ -0,0 +1,315 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Lens and film character, halation glow, light leak and vignette.
+//! Sizes are relative to image, so look is same on any output resolution.
+//! Applied on developed photo before theme, text and frame of theme are never touched.
+
+use super::tone::{self, smoothstep};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+
+/// Vignette on visible frame of photo, post-crop.
+/// Theme cropping photo, e.g. `Letterbox` with `LetterboxFit::Crop`, crops before develop
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Vignette {
+    /// -100 ~ 100, negative darkens and positive lightens corners
+    pub amount: f32,
+    /// 0 ~ 100, distance from center where falloff is half
+    pub midpoint: f32,
+    /// -100 ~ 100, rectangle to circle, 0 is ellipse fit on frame
+    pub roundness: f32,
+    /// 0 ~ 100, hard to soft edge
+    pub feather: f32,
+}
+
+impl core::default::Default for Vignette {
+    fn default() -> Self {
+        Self {
+            amount: 0.0,
+            midpoint: 50.0,
+            roundness: 0.0,
+            feather: 50.0,
+        }
+    }
+}
+
+impl Vignette {
+    pub fn is_identity(&self) -> bool {
+        self.amount == 0.0
+    }
+
+    /// Per pixel function on linear light for image of `width` x `height`
+    fn curve(&self, width: u32, height: u32) -> impl Fn(u32, u32, [f32; 3]) -> [f32; 3] {
+        let (w, h) = (width.max(1) as f32, height.max(1) as f32);
+        let amount = self.amount.clamp(-100.0, 100.0) / 100.0;
+        let roundness = self.roundness.clamp(-100.0, 100.0) / 100.0;
+        // Circle uses half of long edge on both axis
+        let circle = roundness.max(0.0);
+        let scale = [
+            1.0 + (w / w.max(h) - 1.0) * circle,
+            1.0 + (h / w.max(h) - 1.0) * circle,
+        ];
+        // Exponent of superellipse, bigger is closer to rectangle
+        let power = 2.0 - roundness.min(0.0) * 6.0;
+        let midpoint = 0.5 + self.midpoint.clamp(0.0, 100.0) / 100.0;
+        let half_width = (0.05 + self.feather.clamp(0.0, 100.0) / 100.0 * 1.2) / 2.0;
+
+        move |x, y, rgb| {
+            let u = ((x as f32 + 0.5) / w * 2.0 - 1.0).abs() * scale[0];
+            let v = ((y as f32 + 0.5) / h * 2.0 - 1.0).abs() * scale[1];
+            let distance = (u.powf(power) + v.powf(power)).powf(1.0 / power);
+            let t = smoothstep(midpoint - half_width, midpoint + half_width, distance) * amount;
+
+            if t < 0.0 {
+                rgb.map(|x| x * (1.0 + t))
+            } else {
+                rgb.map(|x| x + (1.0 - x).max(0.0) * t)
+            }
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui) {
+        ui.add(
+            egui::Slider::new(&mut self.amount, -100.0..=100.0).text(t!("develop.lens.vignette")),
+        );
+        ui.add_enabled_ui(!self.is_identity(), |ui| {
+            ui.add(
+                egui::Slider::new(&mut self.midpoint, 0.0..=100.0)
+                    .text(t!("develop.lens.midpoint")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.roundness, -100.0..=100.0)
+                    .text(t!("develop.lens.roundness")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.feather, 0.0..=100.0).text(t!("develop.lens.feather")),
+            );
+        });
+    }
+}
+
+/// Colored light bleeding from edge of frame
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LightLeak {
+    /// 0 ~ 100, no leak on 0
+    pub amount: f32,
+    pub color: egui::Color32,
+    /// Edge of leak in degree, 0 is top and 90 is right
+    pub angle: f32,
+    /// 0 ~ 100, reach into frame in percent
+    pub size: f32,
+}
+
+impl core::default::Default for LightLeak {
+    fn default() -> Self {
+        Self {
+            amount: 0.0,
+            color: egui::Color32::from_rgb(0xFF, 0x60, 0x20),
+            angle: 45.0,
+            size: 40.0,
+        }
+    }
+}
+
+impl LightLeak {
+    pub fn is_identity(&self) -> bool {
+        self.amount <= 0.0
+    }
+
+    /// Per pixel function on sRGB encoded 0.0 ..= 1.0, screen blended like light on film
+    fn curve(&self, width: u32, height: u32) -> impl Fn(u32, u32, [f32; 3]) -> [f32; 3] {
+        let (w, h) = (width.max(1) as f32, height.max(1) as f32);
+        // Direction into frame, away from leaking edge
+        let theta = self.angle.to_radians();
+        let (dx, dy) = (-theta.sin(), theta.cos());
+        let corners = [(0.0, 0.0), (w, 0.0), (0.0, h), (w, h)].map(|(x, y)| x * dx + y * dy);
+        let start = corners.iter().copied().fold(f32::MAX, f32::min);
+        let end = corners.iter().copied().fold(f32::MIN, f32::max);
+        let reach = ((end - start) * self.size.clamp(1.0, 100.0) / 100.0).max(1.0);
+
+        let amount = self.amount.clamp(0.0, 100.0) / 100.0;
+        let color = [self.color.r(), self.color.g(), self.color.b()].map(|x| x as f32 / 255.0);
+
+        move |x, y, rgb| {
+            let along = ((x as f32 + 0.5) * dx + (y as f32 + 0.5) * dy - start) / reach;
+            let strength = (1.0 - along).max(0.0).powi(2) * amount;
+            std::array::from_fn(|c| 1.0 - (1.0 - rgb[c]) * (1.0 - color[c] * strength))
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::Slider::new(&mut self.amount, 0.0..=100.0)
+                    .text(t!("develop.lens.light_leak")),
+            );
+            egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut self.color,
+                egui::color_picker::Alpha::Opaque,
+            );
+        });
+        ui.add_enabled_ui(!self.is_identity(), |ui| {
+            ui.add(
+                egui::Slider::new(&mut self.angle, 0.0..=360.0)
+                    .suffix("°")
+                    .text(t!("develop.lens.angle")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.size, 0.0..=100.0)
+                    .suffix("%")
+                    .text(t!("develop.lens.size")),
+            );
+        });
+    }
+}
+
+/// Glow around highlights, light scattered back from film base
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Halation {
+    /// 0 ~ 100, no glow on 0
+    pub amount: f32,
+    /// 0 ~ 100, lightness where glow starts
+    pub threshold: f32,
+    /// 0 ~ 100, spread of glow
+    pub radius: f32,
+    pub color: egui::Color32,
+}
+
+impl core::default::Default for Halation {
+    fn default() -> Self {
+        Self {
+            amount: 0.0,
+            threshold: 70.0,
+            radius: 30.0,
+            color: egui::Color32::from_rgb(0xFF, 0x40, 0x10),
+        }
+    }
+}
+
+impl Halation {
+    pub fn is_identity(&self) -> bool {
+        self.amount <= 0.0
+    }
+
+    fn apply(&self, image: &mut image::RgbaImage) {
+        use image::imageops::FilterType;
+
+        let (width, height) = image.dimensions();
+        let short = width.min(height).max(1) as f32;
+        let sigma = short * (0.002 + self.radius.clamp(0.0, 100.0) / 100.0 * 0.03);
+
+        // Glow is smooth, blur on small copy then enlarge
+        let down = (sigma / 4.0).max(1.0);
+        let (small_w, small_h) = (
+            ((width as f32 / down).round() as u32).max(1),
+            ((height as f32 / down).round() as u32).max(1),
+        );
+        let small = image::imageops::resize(image, small_w, small_h, FilterType::Triangle);
+
+        let threshold = self.threshold.clamp(0.0, 99.0) / 100.0;
+        let highlights =
+            image::ImageBuffer::<image::Luma<f32>, Vec<f32>>::from_fn(small_w, small_h, |x, y| {
+                let px = small.get_pixel(x, y);
+                let lum = tone::luminance([px[0], px[1], px[2]].map(tone::decode));
+                image::Luma([lum * smoothstep(threshold, 1.0, lum.powf(1.0 / 2.2))])
+            });
+        let glow = image::imageops::resize(
+            &image::imageops::fast_blur(&highlights, sigma / down),
+            width,
+            height,
+            FilterType::Triangle,
+        );
+
+        let strength = self.amount.clamp(0.0, 100.0) / 100.0 * 2.0;
+        let color = [self.color.r(), self.color.g(), self.color.b()].map(tone::decode);
+        for (px, g) in image.pixels_mut().zip(glow.pixels()) {
+            for c in 0..3 {
+                px[c] = tone::encode(tone::decode(px[c]) + g[0] * color[c] * strength);
+            }
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::Slider::new(&mut self.amount, 0.0..=100.0).text(t!("develop.lens.halation")),
+            );
+            egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut self.color,
+                egui::color_picker::Alpha::Opaque,
+            );
+        });
+        ui.add_enabled_ui(!self.is_identity(), |ui| {
+            ui.add(
+                egui::Slider::new(&mut self.threshold, 0.0..=100.0)
+                    .text(t!("develop.lens.threshold")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.radius, 0.0..=100.0).text(t!("develop.lens.radius")),
+            );
+        });
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Lens {
+    pub halation: Halation,
+    pub light_leak: LightLeak,
+    pub vignette: Vignette,
+}
+
+impl core::default::Default for Lens {
+    fn default() -> Self {
+        Self {
+            halation: Halation::default(),
+            light_leak: LightLeak::default(),
+            vignette: Vignette::default(),
+        }
+    }
+}
+
+impl Lens {
+    pub fn is_identity(&self) -> bool {
+        self.halation.is_identity() && self.light_leak.is_identity() && self.vignette.is_identity()
+    }
+
+    /// Glow first as it comes from scene highlights, vignette last darkens everything
+    pub fn apply(&self, image: &mut image::RgbaImage) {
+        let (width, height) = image.dimensions();
+
+        if !self.halation.is_identity() {
+            self.halation.apply(image);
+        }
+
+        if !self.light_leak.is_identity() {
+            let curve = self.light_leak.curve(width, height);
+            super::map_pixels(image, |x, y, rgb| {
+                curve(x, y, rgb.map(|x| x as f32 / 255.0))
+                    .map(|x| (x.clamp(0.0, 1.0) * 255.0).round() as u8)
+            });
+        }
+
+        if !self.vignette.is_identity() {
+            let curve = self.vignette.curve(width, height);
+            super::map_pixels(image, |x, y, rgb| {
+                curve(x, y, rgb.map(tone::decode)).map(tone::encode)
+            });
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui) {
+        self.vignette.update_ui(ui);
+        self.light_leak.update_ui(ui);
+        self.halation.update_ui(ui);
+    }
+}
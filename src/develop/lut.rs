@@ -0,0 +1,400 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! 3D LUT color grading from Adobe/Resolve `.cube` files in LUT folder,
+//! e.g. `~/.config/chama-optics/luts`. Any size works, 17, 33 and 65 points are common.
+//! LUT is applied on sRGB encoded values as grading LUTs for Rec.709 expect.
+
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use strum::{EnumIter, IntoEnumIterator};
+
+#[derive(EnumIter, Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Interpolation {
+    Trilinear,
+    /// Smoother on neutral axis and cheaper, same as most grading software
+    Tetrahedral,
+}
+
+impl Interpolation {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::Trilinear => t!("develop.lut.trilinear"),
+            Self::Tetrahedral => t!("develop.lut.tetrahedral"),
+        }
+    }
+}
+
+/// Parsed 3D LUT
+pub(crate) struct Cube {
+    size: usize,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    /// Red changes fastest, then green, then blue
+    table: Vec<[f32; 3]>,
+}
+
+impl Cube {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut table = Vec::new();
+
+        let triple = |args: &[&str], line: usize| -> Result<[f32; 3], String> {
+            match args {
+                [r, g, b] => {
+                    let parse = |x: &str| {
+                        x.parse::<f32>()
+                            .map_err(|e| format!("line {line}: {e} on \"{x}\""))
+                    };
+                    Ok([parse(r)?, parse(g)?, parse(b)?])
+                }
+                _ => Err(format!("line {line}: expected 3 numbers")),
+            }
+        };
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some(keyword) = line.split_whitespace().next() else {
+                continue;
+            };
+            let args = line.split_whitespace().skip(1).collect::<Vec<_>>();
+
+            match keyword {
+                "TITLE" | "LUT_3D_INPUT_RANGE" => {}
+                "LUT_1D_SIZE" => return Err("1D LUT is not supported".to_owned()),
+                "LUT_3D_SIZE" => {
+                    let n = args
+                        .first()
+                        .and_then(|x| x.parse::<usize>().ok())
+                        .filter(|x| (2..=256).contains(x))
+                        .ok_or_else(|| format!("line {}: invalid LUT_3D_SIZE", idx + 1))?;
+                    size = Some(n);
+                    table.reserve(n * n * n);
+                }
+                "DOMAIN_MIN" => domain_min = triple(&args, idx + 1)?,
+                "DOMAIN_MAX" => domain_max = triple(&args, idx + 1)?,
+                _ if keyword.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.') => {
+                    let values = line.split_whitespace().collect::<Vec<_>>();
+                    table.push(triple(&values, idx + 1)?);
+                }
+                // Unknown keywords are vendor extensions
+                _ => {}
+            }
+        }
+
+        let size = size.ok_or("missing LUT_3D_SIZE")?;
+        if table.len() != size * size * size {
+            return Err(format!(
+                "expected {} entries, found {}",
+                size * size * size,
+                table.len()
+            ));
+        }
+
+        Ok(Self {
+            size,
+            domain_min,
+            domain_max,
+            table,
+        })
+    }
+
+    fn at(&self, r: usize, g: usize, b: usize) -> [f32; 3] {
+        self.table[r + self.size * (g + self.size * b)]
+    }
+
+    /// Look up color in 0.0 ..= 1.0
+    pub fn sample(&self, rgb: [f32; 3], interpolation: Interpolation) -> [f32; 3] {
+        let last = (self.size - 1) as f32;
+        let pos: [f32; 3] = std::array::from_fn(|c| {
+            let range = (self.domain_max[c] - self.domain_min[c]).max(f32::EPSILON);
+            ((rgb[c] - self.domain_min[c]) / range).clamp(0.0, 1.0) * last
+        });
+        // Lower corner of cell and position inside of cell
+        let base = pos.map(|x| (x.floor() as usize).min(self.size - 2));
+        let [fr, fg, fb] = std::array::from_fn(|c| pos[c] - base[c] as f32);
+        let corner = |r: usize, g: usize, b: usize| self.at(base[0] + r, base[1] + g, base[2] + b);
+
+        let lerp = |a: [f32; 3], b: [f32; 3], t: f32| -> [f32; 3] {
+            std::array::from_fn(|c| a[c] + (b[c] - a[c]) * t)
+        };
+
+        match interpolation {
+            Interpolation::Trilinear => {
+                let c00 = lerp(corner(0, 0, 0), corner(1, 0, 0), fr);
+                let c10 = lerp(corner(0, 1, 0), corner(1, 1, 0), fr);
+                let c01 = lerp(corner(0, 0, 1), corner(1, 0, 1), fr);
+                let c11 = lerp(corner(0, 1, 1), corner(1, 1, 1), fr);
+                lerp(lerp(c00, c10, fg), lerp(c01, c11, fg), fb)
+            }
+            Interpolation::Tetrahedral => {
+                // Walk from black corner to white corner along largest fraction first
+                let c000 = corner(0, 0, 0);
+                let c111 = corner(1, 1, 1);
+                let (t, c1, c2) = if fr > fg {
+                    if fg > fb {
+                        ([fr, fg, fb], corner(1, 0, 0), corner(1, 1, 0))
+                    } else if fr > fb {
+                        ([fr, fb, fg], corner(1, 0, 0), corner(1, 0, 1))
+                    } else {
+                        ([fb, fr, fg], corner(0, 0, 1), corner(1, 0, 1))
+                    }
+                } else if fb > fg {
+                    ([fb, fg, fr], corner(0, 0, 1), corner(0, 1, 1))
+                } else if fb > fr {
+                    ([fg, fb, fr], corner(0, 1, 0), corner(0, 1, 1))
+                } else {
+                    ([fg, fr, fb], corner(0, 1, 0), corner(1, 1, 0))
+                };
+                std::array::from_fn(|c| {
+                    c000[c] * (1.0 - t[0])
+                        + c1[c] * (t[0] - t[1])
+                        + c2[c] * (t[1] - t[2])
+                        + c111[c] * t[2]
+                })
+            }
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Loaded LUTs by file name, `None` when failed to load
+    static ref CUBES: std::sync::Mutex<std::collections::HashMap<String, Option<Arc<Cube>>>> =
+        std::sync::Mutex::new(std::collections::HashMap::new());
+}
+
+fn luts_folder() -> Option<std::path::PathBuf> {
+    crate::theme::user_folder("luts")
+}
+
+/// File names of `.cube` in LUT folder
+fn lut_names() -> Vec<String> {
+    let Some(entries) = luts_folder().and_then(|x| std::fs::read_dir(x).ok()) else {
+        return vec![];
+    };
+
+    let mut names = entries
+        .filter_map(|x| x.ok().map(|x| x.path()))
+        .filter(|x| {
+            x.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("cube"))
+        })
+        .filter_map(|x| x.file_name().map(|x| x.to_string_lossy().into_owned()))
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+/// Forget loaded LUTs, so changed files are loaded again
+fn reload_luts() {
+    CUBES.lock().unwrap().clear();
+}
+
+pub(crate) fn load_cube(name: &str) -> Option<Arc<Cube>> {
+    CUBES
+        .lock()
+        .unwrap()
+        .entry(name.to_owned())
+        .or_insert_with(|| {
+            let path = luts_folder()?.join(name);
+            let loaded = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| Cube::parse(&text));
+
+            match loaded {
+                Ok(cube) => Some(Arc::new(cube)),
+                Err(e) => {
+                    log::error!("Failed to load LUT {}: {e}", path.display());
+                    None
+                }
+            }
+        })
+        .clone()
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Lut {
+    /// File name in LUT folder, no LUT when `None`
+    pub name: Option<String>,
+    /// Mix with ungraded color in percent
+    pub strength: f32,
+    pub interpolation: Interpolation,
+}
+
+impl core::default::Default for Lut {
+    fn default() -> Self {
+        Self {
+            name: None,
+            strength: 100.0,
+            interpolation: Interpolation::Tetrahedral,
+        }
+    }
+}
+
+impl Lut {
+    pub fn is_identity(&self) -> bool {
+        self.name.is_none() || self.strength <= 0.0
+    }
+
+    /// Per pixel function on sRGB encoded 0.0 ..= 1.0, `None` when nothing to do
+    pub(crate) fn curve(&self) -> Option<impl Fn([f32; 3]) -> [f32; 3]> {
+        if self.is_identity() {
+            return None;
+        }
+        let cube = load_cube(self.name.as_deref()?)?;
+        let (strength, interpolation) =
+            ((self.strength / 100.0).clamp(0.0, 1.0), self.interpolation);
+
+        Some(move |rgb: [f32; 3]| {
+            let graded = cube.sample(rgb, interpolation);
+            std::array::from_fn(|c| rgb[c] + (graded[c] - rgb[c]) * strength)
+        })
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui, id_salt: impl std::hash::Hash) {
+        ui.horizontal(|ui| {
+            ui.label(t!("develop.lut.label"));
+            egui::ComboBox::from_id_salt(id_salt)
+                .selected_text(
+                    self.name
+                        .clone()
+                        .unwrap_or_else(|| t!("develop.lut.none").into()),
+                )
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.name, None, t!("develop.lut.none"));
+                    for name in lut_names() {
+                        ui.selectable_value(&mut self.name, Some(name.clone()), name);
+                    }
+                })
+                .response
+                .on_hover_text(t!(
+                    "develop.lut.description",
+                    path = luts_folder()
+                        .map(|x| x.display().to_string())
+                        .unwrap_or_default()
+                ));
+
+            if ui.button(t!("develop.lut.reload")).clicked() {
+                reload_luts();
+            }
+        });
+
+        ui.add_enabled_ui(self.name.is_some(), |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::Slider::new(&mut self.strength, 0.0..=100.0)
+                        .suffix("%")
+                        .text(t!("develop.lut.strength")),
+                );
+                for interpolation in Interpolation::iter() {
+                    ui.radio_value(
+                        &mut self.interpolation,
+                        interpolation,
+                        interpolation.label(),
+                    );
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Identity LUT text of `size` points with optional header lines
+    fn identity_text(size: usize, header: &str) -> String {
+        let last = (size - 1) as f32;
+        let mut text = format!("TITLE \"identity\"\n# comment\n{header}LUT_3D_SIZE {size}\n");
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let [r, g, b] = [r, g, b].map(|x| x as f32 / last);
+                    text.push_str(&format!("{r} {g} {b}\n"));
+                }
+            }
+        }
+        text
+    }
+
+    fn assert_close(actual: [f32; 3], expected: [f32; 3]) {
+        for c in 0..3 {
+            assert!(
+                (actual[c] - expected[c]).abs() < 1e-5,
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_header_and_table() {
+        let cube = Cube::parse(&identity_text(
+            3,
+            "DOMAIN_MIN 0 0 0\nDOMAIN_MAX 1 2 4 # per channel\nVENDOR_KEYWORD 1\n",
+        ))
+        .unwrap();
+        assert_eq!(cube.size, 3);
+        assert_eq!(cube.domain_min, [0.0; 3]);
+        assert_eq!(cube.domain_max, [1.0, 2.0, 4.0]);
+        assert_eq!(cube.table.len(), 27);
+        // Red changes fastest
+        assert_eq!(cube.at(1, 0, 0), [0.5, 0.0, 0.0]);
+        assert_eq!(cube.at(0, 0, 2), [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let mut short = identity_text(2, "");
+        short.truncate(short.trim_end().rfind('\n').unwrap());
+        assert_eq!(
+            Cube::parse(&short).err().as_deref(),
+            Some("expected 8 entries, found 7")
+        );
+
+        assert!(Cube::parse(&identity_text(2, "").replace("LUT_3D_SIZE 2\n", "")).is_err());
+        assert!(
+            Cube::parse(&identity_text(2, "").replace("LUT_3D_SIZE 2", "LUT_3D_SIZE 1")).is_err()
+        );
+        assert!(Cube::parse("LUT_1D_SIZE 2\n0 0 0\n1 1 1\n").is_err());
+        assert!(Cube::parse(&identity_text(2, "DOMAIN_MAX 1 1\n")).is_err());
+        assert!(Cube::parse(&identity_text(2, "").replace("1 1 1", "1 x 1")).is_err());
+    }
+
+    #[test]
+    fn identity_round_trip() {
+        let colors = [
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+            [0.5, 0.5, 0.5],
+            [0.9, 0.2, 0.4],
+            [0.1, 0.7, 0.3],
+            [0.33, 0.66, 0.99],
+        ];
+        for size in [2, 5, 17] {
+            let cube = Cube::parse(&identity_text(size, "")).unwrap();
+            for interpolation in Interpolation::iter() {
+                for rgb in colors {
+                    assert_close(cube.sample(rgb, interpolation), rgb);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sample_scales_domain() {
+        let cube = Cube::parse(&identity_text(2, "DOMAIN_MAX 2 2 2\n")).unwrap();
+        for interpolation in Interpolation::iter() {
+            assert_close(
+                cube.sample([1.0, 0.5, 2.0], interpolation),
+                [0.5, 0.25, 1.0],
+            );
+        }
+    }
+}
@@ -0,0 +1,229 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Develop stage between decode and theme, adjustments on scaled and oriented photo.
+//! Global settings live in `ExportConfig`, each image can override them with its own.
+//! Presets are JSON files in presets folder, e.g. `~/.config/chama-optics/presets`
+
+pub(crate) mod grain;
+pub(crate) mod lens;
+pub(crate) mod lut;
+pub(crate) mod monochrome;
+pub(crate) mod tone;
+
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Develop {
+    pub tone: tone::Tone,
+    /// Black and white after LUT and lens, on linear light, so nothing adds color back
+    pub mono: monochrome::Monochrome,
+    /// After tone, on sRGB encoded values
+    pub lut: lut::Lut,
+    /// Spatial effects after color
+    pub lens: lens::Lens,
+    /// Last, on sRGB encoded values, luminance only on black and white
+    pub grain: grain::Grain,
+}
+
+impl core::default::Default for Develop {
+    fn default() -> Self {
+        Self {
+            tone: tone::Tone::default(),
+            mono: monochrome::Monochrome::default(),
+            lut: lut::Lut::default(),
+            lens: lens::Lens::default(),
+            grain: grain::Grain::default(),
+        }
+    }
+}
+
+impl Develop {
+    pub fn is_identity(&self) -> bool {
+        self.tone.is_identity()
+            && self.mono.is_identity()
+            && self.lut.is_identity()
+            && self.lens.is_identity()
+            && self.grain.is_identity()
+    }
+
+    /// `seed` picks grain pattern, same seed gives byte-identical result
+    pub fn apply(&self, image: &mut image::RgbaImage, seed: u64) {
+        let tone = (!self.tone.is_identity()).then(|| self.tone.curve());
+        let lut = self.lut.curve();
+        if tone.is_some() || lut.is_some() {
+            map_pixels(image, |_, _, mut rgb| {
+                if let Some(curve) = &tone {
+                    rgb = curve(rgb.map(tone::decode)).map(tone::encode);
+                }
+                if let Some(curve) = &lut {
+                    rgb = curve(rgb.map(|x| x as f32 / 255.0))
+                        .map(|x| (x.clamp(0.0, 1.0) * 255.0).round() as u8);
+                }
+                rgb
+            });
+        }
+
+        self.lens.apply(image);
+
+        // Untoned black and white stays neutral, so it is written as grayscale file
+        let mono = !self.mono.is_identity();
+        if mono {
+            let curve = self.mono.curve();
+            map_pixels(image, |_, _, rgb| {
+                curve(rgb.map(tone::decode)).map(tone::encode)
+            });
+        }
+
+        if !self.grain.is_identity() {
+            let grain = grain::Grain {
+                color: if mono { 0.0 } else { self.grain.color },
+                ..self.grain.clone()
+            };
+            let curve = grain.curve(image.width(), image.height(), seed);
+            map_pixels(image, |x, y, rgb| {
+                curve(x, y, rgb.map(|x| x as f32 / 255.0))
+                    .map(|x| (x.clamp(0.0, 1.0) * 255.0).round() as u8)
+            });
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui, id_salt: impl std::hash::Hash + Copy) {
+        ui.vertical(|ui| {
+            self.tone.update_ui(ui);
+            ui.separator();
+            self.lut.update_ui(ui, (id_salt, "lut"));
+            ui.separator();
+            self.lens.update_ui(ui);
+            ui.separator();
+            self.mono.update_ui(ui, (id_salt, "toning"));
+            ui.separator();
+            self.grain.update_ui(ui);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui.button(t!("develop.reset")).clicked() {
+                    *self = Self::default();
+                }
+
+                egui::ComboBox::from_id_salt((id_salt, "preset"))
+                    .selected_text(t!("develop.load_preset"))
+                    .show_ui(ui, |ui| {
+                        for name in preset_names() {
+                            if ui.selectable_label(false, &name).clicked()
+                                && let Some(preset) = load_preset(&name)
+                            {
+                                *self = preset;
+                            }
+                        }
+                    });
+            });
+        });
+    }
+}
+
+/// Replace RGB of every pixel with `f(x, y, rgb)`, alpha is kept
+pub(crate) fn map_pixels(image: &mut image::RgbaImage, f: impl Fn(u32, u32, [u8; 3]) -> [u8; 3]) {
+    for (x, y, px) in image.enumerate_pixels_mut() {
+        let [r, g, b] = f(x, y, [px[0], px[1], px[2]]);
+        px.0 = [r, g, b, px[3]];
+    }
+}
+
+fn presets_folder() -> Option<std::path::PathBuf> {
+    crate::theme::user_folder("presets")
+}
+
+/// File stem of every `*.json` in presets folder
+fn preset_names() -> Vec<String> {
+    let Some(entries) = presets_folder().and_then(|x| std::fs::read_dir(x).ok()) else {
+        return Vec::new();
+    };
+
+    let mut names = entries
+        .filter_map(|x| x.ok().map(|x| x.path()))
+        .filter(|x| {
+            x.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .filter_map(|x| x.file_stem().map(|x| x.to_string_lossy().to_string()))
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+fn load_preset(name: &str) -> Option<Develop> {
+    let path = presets_folder()?.join(format!("{name}.json"));
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| log::error!("Failed to read preset {}: {e}", path.display()))
+        .ok()?;
+    serde_json::from_str(&text)
+        .map_err(|e| log::error!("Failed to parse preset {}: {e}", path.display()))
+        .ok()
+}
+
+fn save_preset(name: &str, develop: &Develop) -> Result<std::path::PathBuf, anyhow::Error> {
+    let folder = presets_folder().ok_or_else(|| anyhow::anyhow!("No config directory"))?;
+    std::fs::create_dir_all(&folder)?;
+    let path = folder.join(format!("{name}.json"));
+    std::fs::write(&path, serde_json::to_string_pretty(develop)?)?;
+    Ok(path)
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct DevelopConfig {
+    /// Used for every image without its own settings
+    pub global: Develop,
+    /// Name of preset to save
+    #[serde(skip)]
+    preset_name: String,
+}
+
+impl core::default::Default for DevelopConfig {
+    fn default() -> Self {
+        Self {
+            global: Develop::default(),
+            preset_name: "".to_owned(),
+        }
+    }
+}
+
+impl DevelopConfig {
+    pub fn update_ui(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(t!("develop.label"))
+            .id_salt("develop")
+            .show(ui, |ui| {
+                self.global.update_ui(ui, "global_develop");
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.preset_name).desired_width(120.0));
+
+                    let name = self.preset_name.trim();
+                    // File name only, not path
+                    let valid = !name.is_empty() && !name.contains(['/', '\\', '.']);
+                    if ui
+                        .add_enabled(valid, egui::Button::new(t!("develop.save_preset")))
+                        .clicked()
+                    {
+                        match save_preset(name, &self.global) {
+                            Ok(path) => log::info!("Saved preset to {path:?}"),
+                            Err(e) => log::error!("Failed to save preset: {e:?}"),
+                        }
+                    }
+                });
+
+                if let Some(folder) = presets_folder() {
+                    ui.horizontal(|ui| {
+                        ui.label(t!("develop.presets_folder"));
+                        ui.monospace(folder.display().to_string());
+                    });
+                }
+            });
+    }
+}
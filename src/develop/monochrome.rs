@@ -0,0 +1,188 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Black and white conversion with channel mixer and toning, on linear light.
+//! Untoned result has same value on every channel,
+//! then `OutputFormat::save_image` writes it as grayscale file.
+
+use super::tone::{self, smoothstep};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+#[derive(EnumIter, Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Toning {
+    None,
+    Sepia,
+    /// Different hue on shadows and highlights
+    Split,
+}
+
+impl Toning {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::None => t!("develop.mono.toning_none"),
+            Self::Sepia => t!("develop.mono.sepia"),
+            Self::Split => t!("develop.mono.split"),
+        }
+    }
+}
+
+/// Hue of sepia on shadows and highlights in degree
+const SEPIA_HUE: [f32; 2] = [25.0, 40.0];
+
+/// Channel gains of fully saturated hue, normalized to keep luminance
+fn hue_gains(hue: f32, saturation: f32) -> [f32; 3] {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+    let rgb = match hue as u32 {
+        0 => [1.0, x, 0.0],
+        1 => [x, 1.0, 0.0],
+        2 => [0.0, 1.0, x],
+        3 => [0.0, x, 1.0],
+        4 => [x, 0.0, 1.0],
+        _ => [1.0, 0.0, x],
+    };
+    let norm = tone::luminance(rgb);
+    rgb.map(|x| 1.0 + (x / norm - 1.0) * saturation)
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Monochrome {
+    pub enabled: bool,
+    /// Contribution of red, green and blue in percent, -200 ~ 200
+    pub mixer: [f32; 3],
+    pub toning: Toning,
+    /// 0 ~ 100
+    pub sepia: f32,
+    /// Hue in degree, saturation 0 ~ 100
+    pub shadow_hue: f32,
+    pub shadow_saturation: f32,
+    pub highlight_hue: f32,
+    pub highlight_saturation: f32,
+    /// -100 ~ 100, positive gives more range to highlight tone
+    pub balance: f32,
+}
+
+impl core::default::Default for Monochrome {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mixer: [30.0, 60.0, 10.0],
+            toning: Toning::None,
+            sepia: 50.0,
+            shadow_hue: 220.0,
+            shadow_saturation: 20.0,
+            highlight_hue: 40.0,
+            highlight_saturation: 20.0,
+            balance: 0.0,
+        }
+    }
+}
+
+impl Monochrome {
+    pub fn is_identity(&self) -> bool {
+        !self.enabled
+    }
+
+    /// Per pixel function on linear light
+    pub(crate) fn curve(&self) -> impl Fn([f32; 3]) -> [f32; 3] {
+        let mixer = self.mixer.map(|x| x.clamp(-200.0, 200.0) / 100.0);
+        let [shadow, highlight] = match self.toning {
+            Toning::None => [[1.0; 3]; 2],
+            Toning::Sepia => {
+                let saturation = self.sepia.clamp(0.0, 100.0) / 100.0 * 0.5;
+                SEPIA_HUE.map(|hue| hue_gains(hue, saturation))
+            }
+            Toning::Split => [
+                hue_gains(
+                    self.shadow_hue,
+                    self.shadow_saturation.clamp(0.0, 100.0) / 100.0 * 0.5,
+                ),
+                hue_gains(
+                    self.highlight_hue,
+                    self.highlight_saturation.clamp(0.0, 100.0) / 100.0 * 0.5,
+                ),
+            ],
+        };
+        let toned = self.toning != Toning::None;
+        let balance = self.balance.clamp(-100.0, 100.0) / 100.0 * 0.5;
+
+        move |rgb| {
+            let gray = (rgb[0] * mixer[0] + rgb[1] * mixer[1] + rgb[2] * mixer[2]).max(0.0);
+            if !toned {
+                return [gray; 3];
+            }
+
+            let weight = smoothstep(0.0, 1.0, gray.powf(1.0 / 2.2) + balance);
+            std::array::from_fn(|c| gray * (shadow[c] + (highlight[c] - shadow[c]) * weight))
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui, id_salt: impl std::hash::Hash) {
+        ui.checkbox(&mut self.enabled, t!("develop.mono.label"));
+        if !self.enabled {
+            return;
+        }
+
+        for (value, key) in self.mixer.iter_mut().zip([
+            "develop.mono.red",
+            "develop.mono.green",
+            "develop.mono.blue",
+        ]) {
+            ui.add(
+                egui::Slider::new(value, -200.0..=200.0)
+                    .suffix("%")
+                    .text(t!(key)),
+            );
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(t!("develop.mono.toning"));
+            egui::ComboBox::from_id_salt(id_salt)
+                .selected_text(self.toning.label())
+                .show_ui(ui, |ui| {
+                    for toning in Toning::iter() {
+                        ui.selectable_value(&mut self.toning, toning, toning.label());
+                    }
+                });
+        });
+
+        match self.toning {
+            Toning::None => {}
+            Toning::Sepia => {
+                ui.add(
+                    egui::Slider::new(&mut self.sepia, 0.0..=100.0).text(t!("develop.mono.sepia")),
+                );
+            }
+            Toning::Split => {
+                ui.add(
+                    egui::Slider::new(&mut self.highlight_hue, 0.0..=360.0)
+                        .suffix("°")
+                        .text(t!("develop.mono.highlight_hue")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut self.highlight_saturation, 0.0..=100.0)
+                        .text(t!("develop.mono.highlight_saturation")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut self.shadow_hue, 0.0..=360.0)
+                        .suffix("°")
+                        .text(t!("develop.mono.shadow_hue")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut self.shadow_saturation, 0.0..=100.0)
+                        .text(t!("develop.mono.shadow_saturation")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut self.balance, -100.0..=100.0)
+                        .text(t!("develop.mono.balance")),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Tonal and color adjustments, every operation runs on linear light RGB.
+//! Luminance masks of highlights and shadows use perceptual lightness,
+//! but gain is applied on linear values so hue does not shift.
+
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+
+/// Rec.709 luminance of linear sRGB
+pub(crate) const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+/// Middle gray in linear light, pivot of contrast
+const MIDDLE_GRAY: f32 = 0.18;
+/// Entries of linear to sRGB table, fine enough for 8 bit output
+const ENCODE_STEPS: usize = 4096;
+
+lazy_static::lazy_static! {
+    static ref DECODE: [f32; 256] = std::array::from_fn(|x| srgb_to_linear(x as f32 / 255.0));
+    static ref ENCODE: Vec<u8> = (0..=ENCODE_STEPS)
+        .map(|x| (linear_to_srgb(x as f32 / ENCODE_STEPS as f32) * 255.0).round() as u8)
+        .collect();
+}
+
+fn srgb_to_linear(x: f32) -> f32 {
+    if x <= 0.04045 {
+        x / 12.92
+    } else {
+        ((x + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(x: f32) -> f32 {
+    if x <= 0.003_130_8 {
+        x * 12.92
+    } else {
+        1.055 * x.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// 8 bit sRGB to linear light
+pub(crate) fn decode(x: u8) -> f32 {
+    DECODE[x as usize]
+}
+
+/// Linear light to 8 bit sRGB, out of range is clipped
+pub(crate) fn encode(x: f32) -> u8 {
+    ENCODE[(x.clamp(0.0, 1.0) * ENCODE_STEPS as f32).round() as usize]
+}
+
+pub(crate) fn luminance(rgb: [f32; 3]) -> f32 {
+    rgb[0] * LUMA[0] + rgb[1] * LUMA[1] + rgb[2] * LUMA[2]
+}
+
+pub(crate) fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Tone {
+    /// In EV, -3.0 ~ 3.0
+    pub exposure: f32,
+    /// Rest of fields are -100 ~ 100
+    pub contrast: f32,
+    pub highlights: f32,
+    pub shadows: f32,
+    pub saturation: f32,
+    pub vibrance: f32,
+    /// Positive is warmer
+    pub temperature: f32,
+    /// Positive is magenta, negative is green
+    pub tint: f32,
+}
+
+impl core::default::Default for Tone {
+    fn default() -> Self {
+        Self {
+            exposure: 0.0,
+            contrast: 0.0,
+            highlights: 0.0,
+            shadows: 0.0,
+            saturation: 0.0,
+            vibrance: 0.0,
+            temperature: 0.0,
+            tint: 0.0,
+        }
+    }
+}
+
+impl Tone {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Channel gains of white balance, normalized to keep luminance of gray
+    fn white_balance(&self) -> [f32; 3] {
+        let (temp, tint) = (self.temperature / 100.0, self.tint / 100.0);
+        let gains = [
+            2f32.powf(temp * 0.5),
+            2f32.powf(-tint * 0.4),
+            2f32.powf(-temp * 0.5),
+        ];
+        let norm = luminance(gains);
+        gains.map(|x| x / norm)
+    }
+
+    /// Per pixel function on linear light, constants are computed once
+    pub(crate) fn curve(&self) -> impl Fn([f32; 3]) -> [f32; 3] {
+        let exposure = 2f32.powf(self.exposure.clamp(-3.0, 3.0));
+        let gain = self.white_balance().map(|x| x * exposure);
+        let slope = 2f32.powf(self.contrast / 100.0 * 0.7);
+        // In EV at darkest and brightest, masks are wide enough to keep curve monotonic
+        let (shadows, highlights) = (self.shadows / 100.0 * 1.5, self.highlights / 100.0);
+        let (saturation, vibrance) = (self.saturation / 100.0, self.vibrance / 100.0);
+
+        move |rgb| {
+            let mut rgb: [f32; 3] = std::array::from_fn(|c| rgb[c] * gain[c]);
+
+            // Tone of luminance, then scale every channel with same gain
+            let lum = luminance(rgb);
+            if lum > 0.0 {
+                let lightness = lum.powf(1.0 / 2.2);
+                let ev = shadows * (1.0 - smoothstep(0.0, 0.6, lightness))
+                    + highlights * smoothstep(0.3, 1.0, lightness);
+
+                let mut target = lum * 2f32.powf(ev);
+                if slope != 1.0 {
+                    target = MIDDLE_GRAY * (target / MIDDLE_GRAY).powf(slope);
+                }
+                let gain = target / lum;
+                rgb = rgb.map(|x| x * gain);
+            }
+
+            // Saturation around luminance, vibrance pushes dull colors more
+            let lum = luminance(rgb);
+            let max = rgb.iter().copied().fold(0.0, f32::max);
+            let min = rgb.iter().copied().fold(f32::MAX, f32::min);
+            let current = if max > 0.0 { (max - min) / max } else { 0.0 };
+            let amount = (1.0 + saturation + vibrance * (1.0 - current)).max(0.0);
+            rgb.map(|x| (lum + (x - lum) * amount).max(0.0))
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui) {
+        ui.add(
+            egui::Slider::new(&mut self.exposure, -3.0..=3.0)
+                .suffix(" EV")
+                .text(t!("develop.tone.exposure")),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.contrast, -100.0..=100.0).text(t!("develop.tone.contrast")),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.highlights, -100.0..=100.0)
+                .text(t!("develop.tone.highlights")),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.shadows, -100.0..=100.0).text(t!("develop.tone.shadows")),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.saturation, -100.0..=100.0)
+                .text(t!("develop.tone.saturation")),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.vibrance, -100.0..=100.0).text(t!("develop.tone.vibrance")),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.temperature, -100.0..=100.0)
+                .text(t!("develop.tone.temperature")),
+        );
+        ui.add(egui::Slider::new(&mut self.tint, -100.0..=100.0).text(t!("develop.tone.tint")));
+    }
+}
@@ -0,0 +1,467 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Contact sheet (index print), thumbnails of many images on grid of pages
+//! with file name and EXIF caption under each thumbnail.
+//! Page size is in millimeter and rendered on DPI, saved as image per page
+//! with `OutputFormat` or as single multi-page PDF.
+
+use crate::fonts::{FontChain, FontChoice, OverlayFont};
+use crate::packed_image::PackedImage;
+use crate::theme::{template, text_dimensions};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+const MM_PER_INCH: f32 = 25.4;
+const PT_PER_INCH: f32 = 72.0;
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum PageSize {
+    A4,
+    A3,
+    Letter,
+    Custom,
+}
+
+impl PageSize {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::A4 => "A4".into(),
+            Self::A3 => "A3".into(),
+            Self::Letter => "Letter".into(),
+            Self::Custom => t!("export_config.contact_sheet.custom"),
+        }
+    }
+}
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum SheetOutput {
+    /// Each page saved with `OutputFormat`
+    Image,
+    /// All pages in one PDF, pages are JPEG on quality of `OutputFormat`
+    Pdf,
+}
+
+impl SheetOutput {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::Image => t!("export_config.contact_sheet.image_per_page"),
+            Self::Pdf => "PDF".into(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ContactSheet {
+    columns: u32,
+    rows: u32,
+    page: PageSize,
+    landscape: bool,
+    /// Width and height in millimeter on `PageSize::Custom`
+    custom_size: [f32; 2],
+    dpi: u32,
+    /// Page margin in millimeter
+    margin: f32,
+    /// Gap between cells in millimeter
+    spacing: f32,
+    /// Title and page number on top of each page
+    show_header: bool,
+    title: String,
+    /// Position in image list before file name
+    show_number: bool,
+    show_file_name: bool,
+    /// Caption lines under file name, see `template`. Empty line is skipped
+    details: String,
+    settings: String,
+    font: FontChoice,
+    /// Caption size in point
+    font_size: f32,
+    background: egui::Color32,
+    font_color: egui::Color32,
+    output: SheetOutput,
+}
+
+impl core::default::Default for ContactSheet {
+    fn default() -> Self {
+        Self {
+            columns: 4,
+            rows: 5,
+            page: PageSize::A4,
+            landscape: false,
+            custom_size: [200.0, 250.0],
+            dpi: 150,
+            margin: 10.0,
+            spacing: 4.0,
+            show_header: true,
+            title: "".to_owned(),
+            show_number: true,
+            show_file_name: true,
+            details: "[{camera_model}  ][{date} {time}]".to_owned(),
+            settings: template::DEFAULT_SETTINGS.to_owned(),
+            font: FontChoice::Bundled(OverlayFont::Sans),
+            font_size: 6.5,
+            background: egui::Color32::WHITE,
+            font_color: egui::Color32::from_gray(0x30),
+            output: SheetOutput::Pdf,
+        }
+    }
+}
+
+/// Cut end of text with ellipsis until it fits in `max_w`
+fn ellipsize(font: &FontChain, scale: ab_glyph::PxScale, text: &str, max_w: f32) -> String {
+    if text_dimensions(scale, font, text).0 <= max_w {
+        return text.to_owned();
+    }
+
+    let mut chars = text.chars().collect::<Vec<_>>();
+    while chars.pop().is_some() {
+        let cut = format!("{}…", chars.iter().collect::<String>().trim_end());
+        if text_dimensions(scale, font, &cut).0 <= max_w {
+            return cut;
+        }
+    }
+    String::new()
+}
+
+/// Thumbnail fit in `max_w` x `max_h` with EXIF orientation and adjustments applied
+fn thumbnail(
+    pi: &PackedImage,
+    max_w: u32,
+    max_h: u32,
+    export_config: &super::ExportConfig,
+) -> Result<image::RgbaImage, image::ImageError> {
+    let image = pi.get_image()?;
+    let (src_w, src_h) = (image.width(), image.height());
+    // Fit box is rotated together when orientation swaps width and height
+    let (max_w, max_h) = if pi.view_exif.is_vertical_rotated() {
+        (max_h, max_w)
+    } else {
+        (max_w, max_h)
+    };
+    let ratio = (max_w as f32 / src_w.max(1) as f32).min(max_h as f32 / src_h.max(1) as f32);
+
+    let mut thumb = image.thumbnail_exact(
+        ((src_w as f32 * ratio).round() as u32).max(1),
+        ((src_h as f32 * ratio).round() as u32).max(1),
+    );
+    thumb.apply_orientation(pi.view_exif.orientation);
+    let mut thumb = thumb.into_rgba8();
+    pi.apply_develop(export_config, &mut thumb);
+    Ok(thumb)
+}
+
+impl ContactSheet {
+    /// Page size in millimeter
+    fn page_mm(&self) -> [f32; 2] {
+        let [w, h] = match self.page {
+            PageSize::A4 => [210.0, 297.0],
+            PageSize::A3 => [297.0, 420.0],
+            PageSize::Letter => [215.9, 279.4],
+            PageSize::Custom => self.custom_size.map(|x| x.clamp(20.0, 1000.0)),
+        };
+        if self.landscape { [h, w] } else { [w, h] }
+    }
+
+    fn mm_to_px(&self, mm: f32) -> f32 {
+        mm * self.dpi.clamp(36, 600) as f32 / MM_PER_INCH
+    }
+
+    fn per_page(&self) -> usize {
+        (self.columns.max(1) * self.rows.max(1)) as usize
+    }
+
+    fn render_page(
+        &self,
+        images: &[&PackedImage],
+        page: usize,
+        page_count: usize,
+        export_config: &super::ExportConfig,
+    ) -> image::RgbaImage {
+        let [page_w, page_h] = self.page_mm().map(|x| self.mm_to_px(x).round() as u32);
+        let mut canvas = image::RgbaImage::from_pixel(
+            page_w,
+            page_h,
+            crate::theme::color32_to_rgba(self.background),
+        );
+
+        let font = self.font.chain();
+        let font_color = crate::theme::color32_to_rgba(self.font_color);
+        let px_per_pt = self.dpi.clamp(36, 600) as f32 / PT_PER_INCH;
+        let scale = ab_glyph::PxScale::from(self.font_size * px_per_pt);
+        let line_h = font.height(scale).ceil();
+
+        let margin = self.mm_to_px(self.margin);
+        let spacing = self.mm_to_px(self.spacing);
+        let mut top = margin;
+
+        if self.show_header {
+            let header_scale = ab_glyph::PxScale::from(self.font_size * 1.6 * px_per_pt);
+            let counter = format!("{} / {}", page + 1, page_count);
+            let (counter_w, header_h) = text_dimensions(header_scale, &font, &counter);
+            let title = ellipsize(
+                &font,
+                header_scale,
+                self.title.trim(),
+                page_w as f32 - margin * 2.0 - counter_w - spacing,
+            );
+            font.draw_mut(
+                &mut canvas,
+                font_color,
+                margin as i32,
+                top as i32,
+                header_scale,
+                &title,
+            );
+            font.draw_mut(
+                &mut canvas,
+                font_color,
+                (page_w as f32 - margin - counter_w) as i32,
+                top as i32,
+                header_scale,
+                &counter,
+            );
+            top += header_h + spacing;
+        }
+
+        let (columns, rows) = (self.columns.max(1) as f32, self.rows.max(1) as f32);
+        let cell_w =
+            ((page_w as f32 - margin * 2.0 - spacing * (columns - 1.0)) / columns).max(1.0);
+        let cell_h = ((page_h as f32 - top - margin - spacing * (rows - 1.0)) / rows).max(1.0);
+
+        let templates = [&self.details, &self.settings]
+            .into_iter()
+            .filter(|x| !x.trim().is_empty())
+            .collect::<Vec<_>>();
+        let line_count = templates.len() + (self.show_number || self.show_file_name) as usize;
+        let caption_h = if line_count == 0 {
+            0.0
+        } else {
+            line_h * line_count as f32 + line_h * 0.3
+        };
+        let thumb_h = (cell_h - caption_h).max(1.0);
+
+        let start = page * self.per_page();
+        for (idx, pi) in images.iter().skip(start).take(self.per_page()).enumerate() {
+            let (col, row) = (idx as f32 % columns, (idx as f32 / columns).floor());
+            let cell_x = margin + (cell_w + spacing) * col;
+            let cell_y = top + (cell_h + spacing) * row;
+
+            match thumbnail(pi, cell_w as u32, thumb_h as u32, export_config) {
+                Ok(thumb) => {
+                    // Centered on cell, bottom aligned to caption
+                    let (w, h) = thumb.dimensions();
+                    image::imageops::overlay(
+                        &mut canvas,
+                        &thumb,
+                        (cell_x + (cell_w - w as f32) / 2.0) as i64,
+                        (cell_y + thumb_h - h as f32) as i64,
+                    );
+                }
+                Err(e) => {
+                    log::error!("Failed to load {:?} for contact sheet : {e:?}", pi.path);
+                }
+            }
+
+            // Missing values leave empty line, so captions line up across cells
+            let mut lines = Vec::with_capacity(line_count);
+            match (self.show_number, self.show_file_name) {
+                (true, true) => lines.push(format!("{}  {}", pi.sequence, pi.file_name())),
+                (true, false) => lines.push(pi.sequence.to_string()),
+                (false, true) => lines.push(pi.file_name()),
+                (false, false) => {}
+            }
+            for line in templates.iter() {
+                lines.push(template::render(line, &pi.view_exif).trim().to_owned());
+            }
+
+            let mut y = cell_y + thumb_h + line_h * 0.3;
+            for line in lines {
+                let line = ellipsize(&font, scale, &line, cell_w);
+                let (w, _) = text_dimensions(scale, &font, &line);
+                font.draw_mut(
+                    &mut canvas,
+                    font_color,
+                    (cell_x + (cell_w - w) / 2.0) as i32,
+                    y as i32,
+                    scale,
+                    &line,
+                );
+                y += line_h;
+            }
+        }
+
+        canvas
+    }
+
+    /// Save contact sheet of `images` in output folder, returns saved files
+    pub fn save(
+        &self,
+        images: &[&PackedImage],
+        export_config: &super::ExportConfig,
+    ) -> Result<Vec<std::path::PathBuf>, image::ImageError> {
+        let page_count = images.len().div_ceil(self.per_page());
+        let output_name = &export_config.output_name;
+        let file_path = |stem: &str, ext: &str| {
+            output_name.folder.join(format!(
+                "{}{stem}{}.{ext}",
+                output_name.prefix, output_name.postfix
+            ))
+        };
+
+        match self.output {
+            SheetOutput::Image => (0..page_count)
+                .map(|page| {
+                    let path = file_path(
+                        &format!("contact_sheet_{:02}", page + 1),
+                        export_config.output_format.extension(),
+                    );
+                    let canvas = self.render_page(images, page, page_count, export_config);
+                    export_config
+                        .output_format
+                        .save_image(&image::DynamicImage::ImageRgba8(canvas), &path)?;
+                    Ok(path)
+                })
+                .collect(),
+            SheetOutput::Pdf => {
+                let path = file_path("contact_sheet", "pdf");
+                let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+                let mut pdf = super::pdf::PdfWriter::new(file)?;
+                let size_pt = self.page_mm().map(|x| x / MM_PER_INCH * PT_PER_INCH);
+
+                for page in 0..page_count {
+                    let canvas = image::DynamicImage::ImageRgba8(self.render_page(
+                        images,
+                        page,
+                        page_count,
+                        export_config,
+                    ));
+                    let rgb = super::output_format::flatten(&canvas);
+                    let jpeg = super::output_format::encode_jpeg_moz(
+                        &rgb,
+                        export_config.output_format.quality,
+                    )?;
+                    pdf.add_jpeg_page(&jpeg, rgb.width(), rgb.height(), size_pt)?;
+                }
+
+                pdf.finish()?;
+                Ok(vec![path])
+            }
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(t!("export_config.contact_sheet.label"))
+            .id_salt("contact_sheet")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("export_config.contact_sheet.grid"));
+                    ui.add(egui::DragValue::new(&mut self.columns).range(1..=12));
+                    ui.label("×");
+                    ui.add(egui::DragValue::new(&mut self.rows).range(1..=16));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("export_config.contact_sheet.page_size"));
+                    egui::ComboBox::from_id_salt("contact_sheet_page")
+                        .selected_text(self.page.label())
+                        .show_ui(ui, |ui| {
+                            for page in PageSize::iter() {
+                                ui.selectable_value(&mut self.page, page, page.label());
+                            }
+                        });
+                    if self.page == PageSize::Custom {
+                        for value in self.custom_size.iter_mut() {
+                            ui.add(
+                                egui::DragValue::new(value)
+                                    .range(20.0..=1000.0)
+                                    .suffix("mm"),
+                            );
+                        }
+                    }
+                    ui.checkbox(
+                        &mut self.landscape,
+                        t!("export_config.contact_sheet.landscape"),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("DPI");
+                    ui.add(egui::DragValue::new(&mut self.dpi).range(36..=600));
+                    ui.label(t!("export_config.contact_sheet.output"));
+                    egui::ComboBox::from_id_salt("contact_sheet_output")
+                        .selected_text(self.output.label())
+                        .show_ui(ui, |ui| {
+                            for output in SheetOutput::iter() {
+                                ui.selectable_value(&mut self.output, output, output.label());
+                            }
+                        });
+                });
+                ui.add(
+                    egui::Slider::new(&mut self.margin, 0.0..=40.0)
+                        .suffix("mm")
+                        .text(t!("export_config.contact_sheet.margin")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut self.spacing, 0.0..=20.0)
+                        .suffix("mm")
+                        .text(t!("export_config.contact_sheet.spacing")),
+                );
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(
+                        &mut self.show_header,
+                        t!("export_config.contact_sheet.show_header"),
+                    );
+                    ui.add_enabled(
+                        self.show_header,
+                        egui::TextEdit::singleline(&mut self.title).desired_width(160.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(
+                        &mut self.show_number,
+                        t!("export_config.contact_sheet.show_number"),
+                    );
+                    ui.checkbox(
+                        &mut self.show_file_name,
+                        t!("export_config.contact_sheet.show_file_name"),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("export_config.contact_sheet.details"));
+                    template::update_ui(ui, &mut self.details);
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme.settings_template"));
+                    template::update_ui(ui, &mut self.settings);
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme.font"));
+                    self.font.update_ui(ui, "contact_sheet_font");
+                });
+                ui.add(
+                    egui::Slider::new(&mut self.font_size, 3.0..=24.0)
+                        .suffix("pt")
+                        .text(t!("theme.font_size")),
+                );
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme.background_color"));
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.background,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                    ui.label(t!("theme.font_color"));
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.font_color,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                });
+            });
+    }
+}
@@ -6,25 +6,41 @@
 
 use rust_i18n::t;
 
+pub(crate) mod contact_sheet;
 pub(crate) mod output_format;
 pub(crate) mod output_name;
+pub(crate) mod pdf;
+pub(crate) mod photo_booth;
 pub(crate) mod scale_config;
+pub(crate) mod sharpen;
 
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct ExportConfig {
     pub scale_config: scale_config::ScaleConfig,
+    #[serde(default)]
+    pub sharpen: sharpen::Sharpen,
     pub output_format: output_format::OutputFormat,
     pub output_name: output_name::OutputName,
     pub theme_reg: crate::theme::ThemeRegistry,
+    #[serde(default)]
+    pub develop: crate::develop::DevelopConfig,
+    #[serde(default)]
+    pub contact_sheet: contact_sheet::ContactSheet,
+    #[serde(default)]
+    pub photo_booth: photo_booth::PhotoBooth,
 }
 
 impl core::default::Default for ExportConfig {
     fn default() -> Self {
         Self {
             scale_config: scale_config::SCALE_NEAR_COMMON_4K,
+            sharpen: sharpen::Sharpen::default(),
             output_format: output_format::OutputFormat::default(),
             output_name: output_name::OutputName::default(),
             theme_reg: crate::theme::ThemeRegistry::new(),
+            develop: crate::develop::DevelopConfig::default(),
+            contact_sheet: contact_sheet::ContactSheet::default(),
+            photo_booth: photo_booth::PhotoBooth::default(),
         }
     }
 }
@@ -35,12 +51,17 @@ impl ExportConfig {
             ui.heading(t!("export_config.label"));
             ui.separator();
             self.scale_config.update_ui(ui);
+            self.sharpen.update_ui(ui);
             ui.separator();
             self.output_format.update_ui(ui);
             ui.separator();
             self.output_name.update_ui(ui);
             ui.separator();
+            self.develop.update_ui(ui);
+            ui.separator();
             self.theme_reg.update_ui(ui);
+            ui.separator();
+            self.contact_sheet.update_ui(ui);
         });
     }
 }
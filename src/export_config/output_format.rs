@@ -58,26 +58,91 @@ impl core::default::Default for OutputFormat {
     }
 }
 
-fn save_jpeg_moz<P: AsRef<Path>>(
-    img: image::RgbImage,
-    path: P,
+/// Composite transparent pixels over white for format without alpha channel
+pub(crate) fn flatten(img: &DynamicImage) -> image::RgbImage {
+    if !img.color().has_alpha() {
+        return img.to_rgb8();
+    }
+
+    let rgba = img.to_rgba8();
+    image::RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let px = rgba.get_pixel(x, y);
+        let alpha = px[3] as u32;
+        image::Rgb(
+            [px[0], px[1], px[2]]
+                .map(|c| ((c as u32 * alpha + 255 * (255 - alpha) + 127) / 255) as u8),
+        )
+    })
+}
+
+/// Every pixel has same value on red, green and blue, e.g. black and white develop.
+/// Such image is written as grayscale, smaller and without chroma noise
+fn is_neutral(raw: &[u8], channels: usize) -> bool {
+    raw.chunks_exact(channels)
+        .all(|px| px[0] == px[1] && px[1] == px[2])
+}
+
+fn compress_moz(
+    color_space: mozjpeg::ColorSpace,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
     quality: u8,
-) -> Result<(), image::ImageError> {
-    use mozjpeg::ColorSpace;
-    let mut comp = mozjpeg::Compress::new(ColorSpace::JCS_RGB);
-    comp.set_size(img.width() as usize, img.height() as usize);
+) -> Result<Vec<u8>, image::ImageError> {
+    let mut comp = mozjpeg::Compress::new(color_space);
+    comp.set_size(width as usize, height as usize);
     comp.set_quality(quality as f32);
     comp.set_optimize_scans(true);
     comp.set_progressive_mode();
 
     let mut comp = comp.start_compress(Vec::new())?;
-    comp.write_scanlines(&img)?;
-    let jpeg_data = comp.finish()?;
+    comp.write_scanlines(pixels)?;
+    Ok(comp.finish()?)
+}
+
+pub(crate) fn encode_jpeg_moz(
+    img: &image::RgbImage,
+    quality: u8,
+) -> Result<Vec<u8>, image::ImageError> {
+    compress_moz(
+        mozjpeg::ColorSpace::JCS_RGB,
+        img.width(),
+        img.height(),
+        img,
+        quality,
+    )
+}
+
+/// Single channel JPEG
+pub(crate) fn encode_jpeg_moz_gray(
+    img: &image::GrayImage,
+    quality: u8,
+) -> Result<Vec<u8>, image::ImageError> {
+    compress_moz(
+        mozjpeg::ColorSpace::JCS_GRAYSCALE,
+        img.width(),
+        img.height(),
+        img,
+        quality,
+    )
+}
+
+fn save_jpeg_moz<P: AsRef<Path>>(
+    img: image::RgbImage,
+    path: P,
+    quality: u8,
+) -> Result<(), image::ImageError> {
+    let jpeg_data = if is_neutral(&img, 3) {
+        encode_jpeg_moz_gray(&DynamicImage::ImageRgb8(img).to_luma8(), quality)?
+    } else {
+        encode_jpeg_moz(&img, quality)?
+    };
 
     std::fs::write(path, jpeg_data)?;
     Ok(())
 }
 
+/// WebP has no grayscale mode, neutral image is still written as RGB
 fn save_webp<P: AsRef<Path>>(
     img: image::RgbImage,
     path: P,
@@ -96,12 +161,41 @@ fn save_png<P: AsRef<Path>>(img: &DynamicImage, path: P) -> Result<(), image::Im
     let writer = std::io::BufWriter::new(file);
     let encoder = PngEncoder::new_with_quality(writer, CompressionType::Best, FilterType::Adaptive);
 
-    encoder.write_image(
-        &img.to_rgb8(),
-        img.width(),
-        img.height(),
-        image::ExtendedColorType::Rgb8,
-    )?;
+    // Keep alpha channel only when something is transparent
+    let rgba = img.to_rgba8();
+    let neutral = is_neutral(&rgba, 4);
+    if img.color().has_alpha() && rgba.pixels().any(|px| px[3] != 255) {
+        if neutral {
+            let gray_alpha = DynamicImage::ImageRgba8(rgba).to_luma_alpha8();
+            encoder.write_image(
+                &gray_alpha,
+                img.width(),
+                img.height(),
+                image::ExtendedColorType::La8,
+            )?;
+        } else {
+            encoder.write_image(
+                &rgba,
+                img.width(),
+                img.height(),
+                image::ExtendedColorType::Rgba8,
+            )?;
+        }
+    } else if neutral {
+        encoder.write_image(
+            &img.to_luma8(),
+            img.width(),
+            img.height(),
+            image::ExtendedColorType::L8,
+        )?;
+    } else {
+        encoder.write_image(
+            &img.to_rgb8(),
+            img.width(),
+            img.height(),
+            image::ExtendedColorType::Rgb8,
+        )?;
+    }
 
     Ok(())
 }
@@ -113,8 +207,8 @@ impl OutputFormat {
         path: P,
     ) -> Result<(), image::ImageError> {
         match self.ext {
-            OutputExtension::Jpeg => save_jpeg_moz(img.to_rgb8(), path, self.quality),
-            OutputExtension::Webp => save_webp(img.to_rgb8(), path, self.quality),
+            OutputExtension::Jpeg => save_jpeg_moz(flatten(img), path, self.quality),
+            OutputExtension::Webp => save_webp(flatten(img), path, self.quality),
             OutputExtension::PngOptimized => save_png(img, path),
         }
     }
@@ -0,0 +1,141 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Minimal PDF writer, each page is single JPEG image filling whole page.
+//! JPEG is embedded as is with DCTDecode filter, so no PDF library is needed.
+//! Pages are written one by one, only cross reference table stays in memory.
+
+use std::io::Write;
+
+/// Object number of catalog and page tree, written last but numbered first
+const CATALOG_ID: usize = 1;
+const PAGES_ID: usize = 2;
+
+pub(crate) struct PdfWriter<W: Write> {
+    out: W,
+    /// Bytes written so far
+    offset: usize,
+    /// Byte offset of each object, index 0 is object 1
+    objects: Vec<usize>,
+    /// Object number of each page
+    pages: Vec<usize>,
+}
+
+impl<W: Write> PdfWriter<W> {
+    pub fn new(out: W) -> std::io::Result<Self> {
+        let mut writer = Self {
+            out,
+            offset: 0,
+            objects: vec![0; PAGES_ID],
+            pages: Vec::new(),
+        };
+        // Binary comment tells file transfer programs that file is not text
+        writer.write(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n")?;
+        Ok(writer)
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        self.out.write_all(bytes)?;
+        self.offset += bytes.len();
+        Ok(())
+    }
+
+    fn reserve(&mut self) -> usize {
+        self.objects.push(0);
+        self.objects.len()
+    }
+
+    fn begin(&mut self, id: usize) -> std::io::Result<()> {
+        self.objects[id - 1] = self.offset;
+        self.write(format!("{id} 0 obj\n").as_bytes())
+    }
+
+    fn object(&mut self, id: usize, dict: &str) -> std::io::Result<()> {
+        self.begin(id)?;
+        self.write(format!("{dict}\nendobj\n").as_bytes())
+    }
+
+    fn stream(&mut self, id: usize, dict: &str, data: &[u8]) -> std::io::Result<()> {
+        self.begin(id)?;
+        self.write(format!("<< {dict} /Length {} >>\nstream\n", data.len()).as_bytes())?;
+        self.write(data)?;
+        self.write(b"\nendstream\nendobj\n")
+    }
+
+    /// Add page of `size_pt` in point (1/72 inch) showing JPEG of `width` x `height` RGB pixels
+    pub fn add_jpeg_page(
+        &mut self,
+        jpeg: &[u8],
+        width: u32,
+        height: u32,
+        size_pt: [f32; 2],
+    ) -> std::io::Result<()> {
+        let [page_w, page_h] = size_pt;
+        let (page_id, content_id, image_id) = (self.reserve(), self.reserve(), self.reserve());
+
+        self.object(
+            page_id,
+            &format!(
+                "<< /Type /Page /Parent {PAGES_ID} 0 R /MediaBox [0 0 {page_w:.2} {page_h:.2}] \
+                 /Resources << /XObject << /Im0 {image_id} 0 R >> >> /Contents {content_id} 0 R >>"
+            ),
+        )?;
+        self.stream(
+            content_id,
+            "",
+            format!("q {page_w:.2} 0 0 {page_h:.2} 0 0 cm /Im0 Do Q").as_bytes(),
+        )?;
+        self.stream(
+            image_id,
+            &format!(
+                "/Type /XObject /Subtype /Image /Width {width} /Height {height} \
+                 /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /DCTDecode"
+            ),
+            jpeg,
+        )?;
+
+        self.pages.push(page_id);
+        Ok(())
+    }
+
+    /// Write page tree and cross reference table, returns inner writer
+    pub fn finish(mut self) -> std::io::Result<W> {
+        let kids = self
+            .pages
+            .iter()
+            .map(|id| format!("{id} 0 R"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.object(
+            PAGES_ID,
+            &format!(
+                "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+                self.pages.len()
+            ),
+        )?;
+        self.object(
+            CATALOG_ID,
+            &format!("<< /Type /Catalog /Pages {PAGES_ID} 0 R >>"),
+        )?;
+        let info_id = self.reserve();
+        self.object(info_id, "<< /Producer (ChamaOptics) >>")?;
+
+        // Every entry is exactly 20 bytes including line end
+        let xref_offset = self.offset;
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.objects.len() + 1);
+        for offset in &self.objects {
+            xref.push_str(&format!("{offset:010} 00000 n \n"));
+        }
+        xref.push_str(&format!(
+            "trailer\n<< /Size {} /Root {CATALOG_ID} 0 R /Info {info_id} 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            self.objects.len() + 1
+        ));
+        self.write(xref.as_bytes())?;
+
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
@@ -0,0 +1,591 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Photo booth (sticker booth) strip, few loaded images cropped into slots of
+//! 4-cut or 2-cut template on colored frame with decorations and caption.
+//! Slots keep path of image rather than index, so removing image from list
+//! empties its slot instead of shifting others.
+
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::packed_image::PackedImage;
+use crate::theme::{effects, template, text_dimensions};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+const DEFAULT_FONT_SIZE: u32 = 25;
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum BoothLayout {
+    /// 4 cuts in vertical strip
+    Strip4,
+    /// 4 cuts in 2 x 2 grid
+    Grid4,
+    /// 2 cuts in vertical strip
+    Strip2,
+}
+
+impl BoothLayout {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::Strip4 => "1 × 4".into(),
+            Self::Grid4 => "2 × 2".into(),
+            Self::Strip2 => "1 × 2".into(),
+        }
+    }
+
+    /// Columns and rows
+    fn grid(&self) -> (u32, u32) {
+        match self {
+            Self::Strip4 => (1, 4),
+            Self::Grid4 => (2, 2),
+            Self::Strip2 => (1, 2),
+        }
+    }
+
+    fn count(&self) -> usize {
+        let (cols, rows) = self.grid();
+        (cols * rows) as usize
+    }
+}
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Decoration {
+    None,
+    Dots,
+    Stars,
+    Hearts,
+}
+
+impl Decoration {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::None => t!("export_config.photo_booth.decoration_none"),
+            Self::Dots => t!("export_config.photo_booth.dots"),
+            Self::Stars => t!("export_config.photo_booth.stars"),
+            Self::Hearts => t!("export_config.photo_booth.hearts"),
+        }
+    }
+
+    /// Whether point in -1.0 ..= 1.0 square is inside of shape
+    fn contains(&self, x: f32, y: f32) -> bool {
+        match self {
+            Self::None => false,
+            Self::Dots => x * x + y * y <= 1.0,
+            Self::Stars => {
+                // Crossing number on 10 vertices of 5-pointed star, first point upward
+                let vertex = |i: usize| {
+                    let radius = if i.is_multiple_of(2) { 1.0 } else { 0.45 };
+                    let theta = std::f32::consts::PI * i as f32 / 5.0;
+                    (radius * theta.sin(), -radius * theta.cos())
+                };
+                (0..10).fold(false, |inside, i| {
+                    let ((x0, y0), (x1, y1)) = (vertex(i), vertex((i + 1) % 10));
+                    if (y0 > y) != (y1 > y) && x < x0 + (y - y0) * (x1 - x0) / (y1 - y0) {
+                        !inside
+                    } else {
+                        inside
+                    }
+                })
+            }
+            Self::Hearts => {
+                // (x^2 + y^2 - 1)^3 - x^2 y^3 <= 0, flipped and fit into square
+                let (x, y) = (x * 1.2, -y * 1.2 + 0.15);
+                (x * x + y * y - 1.0).powi(3) - x * x * y.powi(3) <= 0.0
+            }
+        }
+    }
+
+    /// Anti-aliased coverage mask of `size` x `size`
+    fn mask(&self, size: u32) -> image::GrayImage {
+        const SAMPLES: u32 = 4;
+        image::GrayImage::from_fn(size, size, |x, y| {
+            let hits = (0..SAMPLES * SAMPLES)
+                .filter(|i| {
+                    let sx =
+                        x as f32 + (i % SAMPLES) as f32 / SAMPLES as f32 + 0.5 / SAMPLES as f32;
+                    let sy =
+                        y as f32 + (i / SAMPLES) as f32 / SAMPLES as f32 + 0.5 / SAMPLES as f32;
+                    self.contains(sx / size as f32 * 2.0 - 1.0, sy / size as f32 * 2.0 - 1.0)
+                })
+                .count() as u32;
+            image::Luma([(hits * 255 / (SAMPLES * SAMPLES)) as u8])
+        })
+    }
+}
+
+/// Image in slot of template
+#[derive(Clone, Default)]
+pub struct BoothSlot {
+    pub path: Option<std::path::PathBuf>,
+    /// Crop position, see `effects::crop_to_aspect`
+    pub pan: [f32; 2],
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct PhotoBooth {
+    layout: BoothLayout,
+    /// Slot width / height
+    slot_ratio: f32,
+    /// Width of each slot in pixel
+    slot_width: u32,
+    /// Frame around slots in percent of slot width
+    border: f32,
+    /// Gap between slots in percent of slot width
+    spacing: f32,
+    /// Caption band under slots in percent of slot width
+    footer: f32,
+    /// Slot corner radius in percent of slot width
+    corner_radius: f32,
+    /// Two same strips side by side, for printing 1-column template on wider paper
+    duplicate: bool,
+    frame_color: egui::Color32,
+    decoration: Decoration,
+    decoration_color: egui::Color32,
+    /// Number of decorations scattered on frame
+    decoration_count: u32,
+    /// Large text on caption band
+    title: String,
+    /// Small text under title, see `template`, rendered with EXIF of first image
+    subtitle: String,
+    font_color: egui::Color32,
+    font: FontChoice,
+    font_size: f32,
+
+    #[serde(skip)]
+    slots: Vec<BoothSlot>,
+}
+
+impl core::default::Default for PhotoBooth {
+    fn default() -> Self {
+        Self {
+            layout: BoothLayout::Strip4,
+            slot_ratio: 4.0 / 3.0,
+            slot_width: 1000,
+            border: 6.0,
+            spacing: 4.0,
+            footer: 28.0,
+            corner_radius: 0.0,
+            duplicate: false,
+            frame_color: egui::Color32::from_rgb(0xFA, 0xD4, 0xDC),
+            decoration: Decoration::Hearts,
+            decoration_color: egui::Color32::WHITE,
+            decoration_count: 14,
+            title: "CHAMA OPTICS".to_owned(),
+            subtitle: "{date}".to_owned(),
+            font_color: egui::Color32::from_gray(0x30),
+            font: FontChoice::Bundled(OverlayFont::Sans),
+            font_size: DEFAULT_FONT_SIZE as f32,
+            slots: Vec::new(),
+        }
+    }
+}
+
+/// Event from composer UI
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PhotoBoothEvent {
+    None,
+    Save,
+}
+
+impl PhotoBooth {
+    /// relative size on slot width
+    fn rel_size<F: Copy + num_traits::AsPrimitive<f32>>(&self, size: F, slot_w: u32) -> f32 {
+        size.as_() * (self.font_size / (DEFAULT_FONT_SIZE as f32)) * (slot_w as f32 / 1000.0)
+    }
+
+    fn filled<'a>(&self, images: &'a [PackedImage]) -> Vec<(Option<&'a PackedImage>, [f32; 2])> {
+        (0..self.layout.count())
+            .map(|idx| {
+                let slot = self.slots.get(idx).cloned().unwrap_or_default();
+                let pi = slot
+                    .path
+                    .and_then(|path| images.iter().find(|pi| pi.path == path));
+                (pi, slot.pan)
+            })
+            .collect()
+    }
+
+    fn compose(
+        &self,
+        images: &[PackedImage],
+        export_config: &super::ExportConfig,
+    ) -> Result<image::RgbaImage, image::ImageError> {
+        let (cols, rows) = self.layout.grid();
+        let slot_w = self.slot_width.clamp(100, 4000);
+        let ratio = self.slot_ratio.clamp(0.3, 3.0);
+        let slot_h = (slot_w as f32 / ratio).round() as u32;
+        let unit = slot_w as f32 / 100.0;
+        let border = (self.border * unit).round() as u32;
+        let spacing = (self.spacing * unit).round() as u32;
+        let footer = (self.footer * unit).round() as u32;
+
+        let strip_w = border * 2 + slot_w * cols + spacing * (cols - 1);
+        let strip_h = border * 2 + slot_h * rows + spacing * (rows - 1) + footer;
+        let mut strip = image::RgbaImage::from_pixel(
+            strip_w,
+            strip_h,
+            crate::theme::color32_to_rgba(self.frame_color),
+        );
+
+        let slot_pos = |idx: u32| {
+            let (col, row) = (idx % cols, idx / cols);
+            (
+                border + (slot_w + spacing) * col,
+                border + (slot_h + spacing) * row,
+            )
+        };
+
+        // Decorations go on frame only, never under slots or caption
+        if self.decoration != Decoration::None {
+            let color = crate::theme::color32_to_rgba(self.decoration_color);
+            let avoid = (0..cols * rows)
+                .map(|idx| {
+                    let (x, y) = slot_pos(idx);
+                    (x, y, slot_w, slot_h)
+                })
+                .chain([(0, strip_h - border - footer, strip_w, footer)])
+                .collect::<Vec<_>>();
+            // Fixed seed keeps same decoration on every save
+            let mut seed = 0x2545_F491_u32;
+            let mut random = move || {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                seed as f32 / u32::MAX as f32
+            };
+
+            let mut placed = 0;
+            for _ in 0..self.decoration_count * 40 {
+                if placed >= self.decoration_count {
+                    break;
+                }
+                let size = ((2.0 + random() * 4.0) * unit).round().max(2.0) as u32;
+                let x = (random() * strip_w.saturating_sub(size) as f32) as u32;
+                let y = (random() * strip_h.saturating_sub(size) as f32) as u32;
+                let overlap = avoid.iter().any(|&(ax, ay, aw, ah)| {
+                    x < ax + aw && ax < x + size && y < ay + ah && ay < y + size
+                });
+                if !overlap {
+                    let mask = self.decoration.mask(size);
+                    crate::theme::blend_mask_mut(&mut strip, &mask, x as i64, y as i64, color);
+                    placed += 1;
+                }
+            }
+        }
+
+        let radius = self.corner_radius * unit;
+        for (idx, (pi, pan)) in self.filled(images).into_iter().enumerate() {
+            let (x, y) = slot_pos(idx as u32);
+            let mut photo = match pi {
+                Some(pi) => {
+                    let mut image = pi.get_image()?;
+                    image.apply_orientation(pi.view_exif.orientation);
+                    let cropped = effects::crop_to_aspect(&image.to_rgba8(), ratio, pan);
+                    let mut photo = image::imageops::resize(
+                        &cropped,
+                        slot_w,
+                        slot_h,
+                        image::imageops::FilterType::CatmullRom,
+                    );
+                    pi.apply_develop(export_config, &mut photo);
+                    photo
+                }
+                // Placeholder on empty slot
+                None => image::RgbaImage::from_pixel(slot_w, slot_h, image::Rgba([0, 0, 0, 40])),
+            };
+            if radius > 0.0 {
+                effects::apply_mask_alpha(
+                    &mut photo,
+                    &effects::rounded_rect_mask(slot_w, slot_h, radius),
+                );
+            }
+            image::imageops::overlay(&mut strip, &photo, x as i64, y as i64);
+        }
+
+        // Caption centered on footer band
+        if footer > 0 {
+            let font = self.font.chain();
+            let color = crate::theme::color32_to_rgba(self.font_color);
+            let exif = self
+                .filled(images)
+                .into_iter()
+                .find_map(|(pi, _)| pi.map(|pi| &pi.view_exif));
+            let subtitle = exif
+                .map(|exif| template::render(&self.subtitle, exif).trim().to_owned())
+                .unwrap_or_default();
+            let lines = [
+                (self.title.trim(), self.rel_size(90, slot_w)),
+                (subtitle.as_str(), self.rel_size(45, slot_w)),
+            ]
+            .into_iter()
+            .filter(|(text, _)| !text.is_empty())
+            .map(|(text, size)| {
+                let scale = ab_glyph::PxScale::from(size);
+                let (w, h) = text_dimensions(scale, &font, text);
+                (text, scale, w, h)
+            })
+            .collect::<Vec<_>>();
+
+            let gap = self.rel_size(12, slot_w);
+            let total_h =
+                lines.iter().map(|x| x.3).sum::<f32>() + gap * lines.len().saturating_sub(1) as f32;
+            // Band is between last slot and bottom border
+            let band_top = (strip_h - border - footer) as f32;
+            let mut y = band_top + (footer as f32 - total_h) / 2.0;
+            for (text, scale, w, h) in lines {
+                font.draw_mut(
+                    &mut strip,
+                    color,
+                    ((strip_w as f32 - w) / 2.0) as i32,
+                    y as i32,
+                    scale,
+                    text,
+                );
+                y += h + gap;
+            }
+        }
+
+        if self.duplicate && cols == 1 {
+            let mut canvas = image::RgbaImage::new(strip_w * 2, strip_h);
+            image::imageops::replace(&mut canvas, &strip, 0, 0);
+            image::imageops::replace(&mut canvas, &strip, strip_w as i64, 0);
+            Ok(canvas)
+        } else {
+            Ok(strip)
+        }
+    }
+
+    /// Save composed strip in output folder, named after image in first slot
+    pub fn save(
+        &self,
+        images: &[PackedImage],
+        export_config: &super::ExportConfig,
+    ) -> Result<std::path::PathBuf, image::ImageError> {
+        let canvas = self.compose(images, export_config)?;
+        let stem = self
+            .filled(images)
+            .into_iter()
+            .find_map(|(pi, _)| pi.map(|pi| pi.path.file_stem().unwrap_or_default().to_owned()))
+            .unwrap_or_default();
+
+        let output_name = &export_config.output_name;
+        let path = output_name.folder.join(format!(
+            "{}{}_booth{}.{}",
+            output_name.prefix,
+            stem.to_string_lossy(),
+            output_name.postfix,
+            export_config.output_format.extension()
+        ));
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), &path)?;
+        Ok(path)
+    }
+
+    fn update_slots_ui(&mut self, ui: &mut egui::Ui, images: &[PackedImage]) {
+        let image_label = |pi: &PackedImage| {
+            images
+                .iter()
+                .position(|x| x.path == pi.path)
+                .map(|idx| format!("{}. {}", idx + 1, pi.file_name()))
+                .unwrap_or_default()
+        };
+
+        for (idx, slot) in self.slots.iter_mut().enumerate() {
+            let current = slot
+                .path
+                .as_ref()
+                .and_then(|path| images.iter().find(|pi| &pi.path == path));
+
+            ui.horizontal(|ui| {
+                ui.label(format!("#{}", idx + 1));
+                egui::ComboBox::from_id_salt(("photo_booth_slot", idx))
+                    .width(180.0)
+                    .selected_text(
+                        current
+                            .map(image_label)
+                            .unwrap_or_else(|| t!("export_config.photo_booth.empty").into()),
+                    )
+                    .show_ui(ui, |ui| {
+                        if ui
+                            .selectable_label(
+                                current.is_none(),
+                                t!("export_config.photo_booth.empty"),
+                            )
+                            .clicked()
+                        {
+                            slot.path = None;
+                        }
+                        for pi in images {
+                            let selected = slot.path.as_ref() == Some(&pi.path);
+                            if ui.selectable_label(selected, image_label(pi)).clicked() {
+                                slot.path = Some(pi.path.clone());
+                                slot.pan = pi.crop_offset;
+                            }
+                        }
+                    });
+                ui.add_enabled_ui(current.is_some(), |ui| {
+                    for value in slot.pan.iter_mut() {
+                        ui.add(
+                            egui::DragValue::new(value)
+                                .range(-100.0..=100.0)
+                                .speed(1.0)
+                                .suffix("%"),
+                        );
+                    }
+                });
+            });
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui, images: &[PackedImage]) -> PhotoBoothEvent {
+        let mut ret = PhotoBoothEvent::None;
+        self.slots
+            .resize_with(self.layout.count(), Default::default);
+
+        egui::CollapsingHeader::new(t!("export_config.photo_booth.label"))
+            .id_salt("photo_booth")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("export_config.photo_booth.layout"));
+                    egui::ComboBox::from_id_salt("photo_booth_layout")
+                        .selected_text(self.layout.label())
+                        .show_ui(ui, |ui| {
+                            for layout in BoothLayout::iter() {
+                                ui.selectable_value(&mut self.layout, layout, layout.label());
+                            }
+                        });
+                    if ui
+                        .button(t!("export_config.photo_booth.fill_in_order"))
+                        .clicked()
+                    {
+                        for (slot, pi) in self.slots.iter_mut().zip(images) {
+                            slot.path = Some(pi.path.clone());
+                            slot.pan = pi.crop_offset;
+                        }
+                    }
+                });
+
+                ui.label(t!("export_config.photo_booth.slots"))
+                    .on_hover_text(t!("export_config.photo_booth.slots_description"));
+                self.update_slots_ui(ui, images);
+
+                ui.horizontal(|ui| {
+                    ui.label(t!("export_config.photo_booth.slot_ratio"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.slot_ratio)
+                            .range(0.3..=3.0)
+                            .speed(0.01)
+                            .suffix(":1"),
+                    );
+                    ui.label(t!("export_config.photo_booth.slot_width"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.slot_width)
+                            .range(100..=4000)
+                            .suffix("px"),
+                    );
+                });
+                ui.add(
+                    egui::Slider::new(&mut self.border, 0.0..=20.0)
+                        .suffix("%")
+                        .text(t!("export_config.photo_booth.border")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut self.spacing, 0.0..=20.0)
+                        .suffix("%")
+                        .text(t!("export_config.photo_booth.spacing")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut self.footer, 0.0..=60.0)
+                        .suffix("%")
+                        .text(t!("export_config.photo_booth.footer")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut self.corner_radius, 0.0..=20.0)
+                        .suffix("%")
+                        .text(t!("theme.blur_canvas_config.corner_radius")),
+                );
+                ui.add_enabled_ui(self.layout.grid().0 == 1, |ui| {
+                    ui.checkbox(
+                        &mut self.duplicate,
+                        t!("export_config.photo_booth.duplicate"),
+                    );
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(t!("export_config.photo_booth.decoration"));
+                    egui::ComboBox::from_id_salt("photo_booth_decoration")
+                        .selected_text(self.decoration.label())
+                        .show_ui(ui, |ui| {
+                            for decoration in Decoration::iter() {
+                                ui.selectable_value(
+                                    &mut self.decoration,
+                                    decoration,
+                                    decoration.label(),
+                                );
+                            }
+                        });
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.decoration_color,
+                        egui::color_picker::Alpha::OnlyBlend,
+                    );
+                    ui.add_enabled(
+                        self.decoration != Decoration::None,
+                        egui::DragValue::new(&mut self.decoration_count).range(1..=60),
+                    );
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(t!("export_config.photo_booth.title"));
+                    ui.add(egui::TextEdit::singleline(&mut self.title).desired_width(160.0));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("export_config.photo_booth.subtitle"));
+                    template::update_ui(ui, &mut self.subtitle);
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme.font"));
+                    self.font.update_ui(ui, "photo_booth_font");
+                });
+                ui.add(
+                    egui::Slider::new(&mut self.font_size, 1.0..=100.0).text(t!("theme.font_size")),
+                )
+                .on_hover_text(t!(
+                    "theme.font_size_description",
+                    default = DEFAULT_FONT_SIZE
+                ));
+                ui.horizontal(|ui| {
+                    ui.label(t!("export_config.photo_booth.frame_color"));
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.frame_color,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                    ui.label(t!("theme.font_color"));
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.font_color,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                });
+
+                if ui
+                    .add_enabled(
+                        self.filled(images).iter().any(|(pi, _)| pi.is_some()),
+                        egui::Button::new(t!("app.default.save")).fill(egui::Color32::GREEN),
+                    )
+                    .clicked()
+                {
+                    ret = PhotoBoothEvent::Save;
+                }
+            });
+
+        ret
+    }
+}
@@ -0,0 +1,142 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Output sharpening, unsharp mask on resized photo before develop and theme.
+//! Only luminance is sharpened, so no color fringe and gray stays gray.
+
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum SharpenPreset {
+    Off,
+    /// Light sharpening for display on monitor and web
+    Screen,
+    /// Stronger sharpening, ink spreads on paper
+    Print,
+    Custom,
+}
+
+impl SharpenPreset {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::Off => t!("export_config.sharpen.off"),
+            Self::Screen => t!("export_config.sharpen.screen"),
+            Self::Print => t!("export_config.sharpen.print"),
+            Self::Custom => t!("export_config.sharpen.custom"),
+        }
+    }
+
+    /// Amount, radius and threshold of preset
+    fn values(&self) -> Option<(f32, f32, u8)> {
+        match self {
+            Self::Off => Some((0.0, 0.5, 0)),
+            Self::Screen => Some((80.0, 0.5, 2)),
+            Self::Print => Some((150.0, 1.0, 3)),
+            Self::Custom => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct Sharpen {
+    pub preset: SharpenPreset,
+    /// Strength in percent, 0 ~ 500
+    pub amount: f32,
+    /// Sigma of blur in pixel of resized image
+    pub radius: f32,
+    /// Difference of luminance below this is not sharpened, keeps smooth area clean
+    pub threshold: u8,
+}
+
+impl core::default::Default for Sharpen {
+    fn default() -> Self {
+        Self {
+            preset: SharpenPreset::Off,
+            amount: 0.0,
+            radius: 0.5,
+            threshold: 0,
+        }
+    }
+}
+
+impl Sharpen {
+    pub fn is_identity(&self) -> bool {
+        self.amount <= 0.0 || self.radius <= 0.0
+    }
+
+    pub fn apply(&self, image: &mut image::RgbaImage) {
+        if self.is_identity() {
+            return;
+        }
+
+        let luma = |px: &image::Rgba<u8>| {
+            (px[0] as f32 * 0.2126) + (px[1] as f32 * 0.7152) + (px[2] as f32 * 0.0722)
+        };
+        let blurred = image::imageops::blur(image, self.radius.clamp(0.1, 5.0));
+        let amount = self.amount.clamp(0.0, 500.0) / 100.0;
+        let threshold = self.threshold as f32;
+
+        for (px, blur) in image.pixels_mut().zip(blurred.pixels()) {
+            let detail = luma(px) - luma(blur);
+            if detail.abs() <= threshold {
+                continue;
+            }
+            let delta = detail * amount;
+            for c in 0..3 {
+                px[c] = (px[c] as f32 + delta).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(t!("export_config.sharpen.label"))
+                .on_hover_text(t!("export_config.sharpen.description"));
+            egui::ComboBox::from_id_salt("sharpen_preset")
+                .selected_text(self.preset.label())
+                .show_ui(ui, |ui| {
+                    for preset in SharpenPreset::iter() {
+                        if ui
+                            .selectable_value(&mut self.preset, preset, preset.label())
+                            .clicked()
+                            && let Some((amount, radius, threshold)) = preset.values()
+                        {
+                            (self.amount, self.radius, self.threshold) =
+                                (amount, radius, threshold);
+                        }
+                    }
+                });
+        });
+
+        if self.preset == SharpenPreset::Off {
+            return;
+        }
+
+        let before = *self;
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::Slider::new(&mut self.amount, 0.0..=500.0)
+                    .suffix("%")
+                    .text(t!("export_config.sharpen.amount")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.radius, 0.1..=5.0)
+                    .suffix(" px")
+                    .text(t!("export_config.sharpen.radius")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.threshold, 0..=32)
+                    .text(t!("export_config.sharpen.threshold")),
+            );
+        });
+        if *self != before {
+            self.preset = SharpenPreset::Custom;
+        }
+    }
+}
@@ -0,0 +1,184 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Font with fallback chain to draw text on image.
+//! Text is split into runs by font that has the glyph (primary, Noto Sans KR, then D2Coding),
+//! each run is shaped by rustybuzz for kerning, ligatures and complex scripts.
+//! Measurement and drawing share same shaped glyphs.
+//! Line metrics (height, ascent) always come from primary font.
+
+use ab_glyph::{Font, FontArc, GlyphId, OutlinedGlyph, PxScale, ScaleFont, point};
+use imageproc::definitions::Clamp;
+use imageproc::drawing::Canvas;
+use std::sync::Arc;
+
+/// Same face for outline (`FontArc`) and shaping (`rustybuzz::Face`), always first face of file.
+/// Shaping face is parsed once, font file is static as bundled or interned user font
+#[derive(Clone)]
+pub struct ChainFont {
+    font: FontArc,
+    face: Arc<rustybuzz::Face<'static>>,
+}
+
+impl ChainFont {
+    /// `None` when font cannot be parsed for shaping
+    pub fn new(font: FontArc, data: &'static [u8]) -> Option<Self> {
+        Some(Self {
+            font,
+            face: Arc::new(rustybuzz::Face::from_slice(data, 0)?),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct FontChain {
+    /// Primary font first
+    fonts: Vec<ChainFont>,
+}
+
+/// Keep with previous character even if other font has it,
+/// combining marks, joiners and variation selectors
+fn is_attached(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{200C}'..='\u{200D}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{3099}'..='\u{309A}'
+    )
+}
+
+impl FontChain {
+    /// Primary font with bundled fallback fonts
+    pub fn new(primary: ChainFont) -> Self {
+        Self {
+            fonts: vec![
+                primary,
+                super::OverlayFont::Sans.chain_font(),
+                super::OverlayFont::Mono.chain_font(),
+            ],
+        }
+    }
+
+    fn primary(&self) -> &FontArc {
+        &self.fonts[0].font
+    }
+
+    /// First font that has glyph of `c`, primary font if nothing has
+    fn font_index(&self, c: char) -> usize {
+        self.fonts
+            .iter()
+            .position(|x| x.font.glyph_id(c) != GlyphId(0))
+            .unwrap_or(0)
+    }
+
+    pub fn ascent(&self, scale: PxScale) -> f32 {
+        self.primary().as_scaled(scale).ascent()
+    }
+
+    pub fn height(&self, scale: PxScale) -> f32 {
+        self.primary().as_scaled(scale).height()
+    }
+
+    /// Split text into (font index, run) by font that has glyphs
+    fn runs<'a>(&self, text: &'a str) -> Vec<(usize, &'a str)> {
+        let mut runs: Vec<(usize, &'a str)> = Vec::new();
+        let mut start = 0;
+        let mut current: Option<usize> = None;
+
+        for (pos, c) in text.char_indices() {
+            let idx = match current {
+                Some(x) if is_attached(c) || c.is_whitespace() => x,
+                _ => self.font_index(c),
+            };
+
+            match current {
+                Some(x) if x != idx => {
+                    runs.push((x, &text[start..pos]));
+                    start = pos;
+                }
+                _ => {}
+            }
+            current = Some(idx);
+        }
+
+        if let Some(x) = current {
+            runs.push((x, &text[start..]));
+        }
+        runs
+    }
+
+    /// Shaped and outlined glyphs on baseline of primary font, and advance width of whole text
+    fn layout(&self, scale: PxScale, text: &str) -> (Vec<OutlinedGlyph>, f32) {
+        let baseline = self.ascent(scale);
+        let mut glyphs = Vec::new();
+        let mut pen = 0.0;
+
+        for (idx, run) in self.runs(text) {
+            let chain_font = &self.fonts[idx];
+            let scaled = chain_font.font.as_scaled(scale);
+            let (h_factor, v_factor) = (scaled.h_scale_factor(), scaled.v_scale_factor());
+
+            let mut buffer = rustybuzz::UnicodeBuffer::new();
+            buffer.push_str(run);
+            buffer.guess_segment_properties();
+            let shaped = rustybuzz::shape(&chain_font.face, &[], buffer);
+
+            for (info, pos) in shaped.glyph_infos().iter().zip(shaped.glyph_positions()) {
+                let x = pen + pos.x_offset as f32 * h_factor;
+                let y = baseline - pos.y_offset as f32 * v_factor;
+                let glyph =
+                    GlyphId(info.glyph_id as u16).with_scale_and_position(scale, point(x, y));
+
+                if let Some(outlined) = chain_font.font.outline_glyph(glyph) {
+                    glyphs.push(outlined);
+                }
+                pen += pos.x_advance as f32 * h_factor;
+            }
+        }
+
+        (glyphs, pen)
+    }
+
+    /// Width and height of single line text
+    pub fn dimensions(&self, scale: PxScale, text: &str) -> (f32, f32) {
+        (self.layout(scale, text).1, self.height(scale))
+    }
+
+    /// Same as `imageproc::drawing::draw_text_mut`, `y` is top of line
+    pub fn draw_mut<C>(
+        &self,
+        canvas: &mut C,
+        color: C::Pixel,
+        x: i32,
+        y: i32,
+        scale: PxScale,
+        text: &str,
+    ) where
+        C: Canvas,
+        <C::Pixel as image::Pixel>::Subpixel: Into<f32> + Clamp<f32>,
+    {
+        let (canvas_w, canvas_h) = (canvas.width() as i32, canvas.height() as i32);
+
+        for glyph in self.layout(scale, text).0 {
+            let bb = glyph.px_bounds();
+            glyph.draw(|gx, gy, gv| {
+                let px = gx as i32 + x + bb.min.x.round() as i32;
+                let py = gy as i32 + y + bb.min.y.round() as i32;
+
+                if (0..canvas_w).contains(&px) && (0..canvas_h).contains(&py) {
+                    let gv = gv.clamp(0.0, 1.0);
+                    let (px, py) = (px as u32, py as u32);
+                    let pixel = canvas.get_pixel(px, py);
+                    let weighted = imageproc::pixelops::weighted_sum(pixel, color, 1.0 - gv, gv);
+                    canvas.draw_pixel(px, py, weighted);
+                }
+            });
+        }
+    }
+}
@@ -9,6 +9,9 @@
 use eframe::egui;
 
 pub(crate) mod builtin_fonts;
+pub(crate) mod chain;
+
+pub use chain::{ChainFont, FontChain};
 
 struct BuiltInFonts {
     pub(crate) name: &'static str,
@@ -25,8 +28,236 @@ const FONT_NTSANS_MED: BuiltInFonts = BuiltInFonts {
     data: include_bytes!("../../assets/fonts/NotoSansKR-Medium.ttf"),
 };
 
+const FONT_DIGITS_DATA: &[u8] = include_bytes!(env!("DIGITAL_7_FONT_PATH"));
+
 lazy_static::lazy_static! {
-    pub static ref FONT_DIGITS: ab_glyph::FontArc = ab_glyph::FontArc::try_from_slice(include_bytes!(env!("DIGITAL_7_FONT_PATH"))).expect("Cannot init font.");
+    pub static ref FONT_DIGITS: ab_glyph::FontArc = ab_glyph::FontArc::try_from_slice(FONT_DIGITS_DATA).expect("Cannot init font.");
+    pub static ref FONT_SANS: ab_glyph::FontArc = ab_glyph::FontArc::try_from_slice(FONT_NTSANS_MED.data).expect("Cannot init font.");
+    pub static ref FONT_MONO: ab_glyph::FontArc = ab_glyph::FontArc::try_from_slice(FONT_D2CODING.data).expect("Cannot init font.");
+    static ref CHAIN_SANS: ChainFont = ChainFont::new(FONT_SANS.clone(), FONT_NTSANS_MED.data).expect("Cannot init font.");
+    static ref CHAIN_DIGITS: ChainFont = ChainFont::new(FONT_DIGITS.clone(), FONT_DIGITS_DATA).expect("Cannot init font.");
+    static ref CHAIN_MONO: ChainFont = ChainFont::new(FONT_MONO.clone(), FONT_D2CODING.data).expect("Cannot init font.");
+}
+
+/// Bundled fonts to draw on image
+#[derive(
+    strum::EnumIter, Clone, Copy, Debug, serde::Deserialize, serde::Serialize, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayFont {
+    /// Noto Sans KR Medium
+    Sans,
+    /// Digital-7
+    Digits,
+    /// D2Coding
+    Mono,
+}
+
+impl OverlayFont {
+    pub fn font(&self) -> ab_glyph::FontArc {
+        match self {
+            Self::Sans => FONT_SANS.clone(),
+            Self::Digits => FONT_DIGITS.clone(),
+            Self::Mono => FONT_MONO.clone(),
+        }
+    }
+
+    /// Shaping face is parsed once and shared
+    pub fn chain_font(&self) -> ChainFont {
+        match self {
+            Self::Sans => CHAIN_SANS.clone(),
+            Self::Digits => CHAIN_DIGITS.clone(),
+            Self::Mono => CHAIN_MONO.clone(),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Sans => FONT_NTSANS_MED.name,
+            Self::Digits => "Digital-7",
+            Self::Mono => FONT_D2CODING.name,
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Self::Sans => "sans",
+            Self::Digits => "digits",
+            Self::Mono => "mono",
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Loaded user fonts by file name, `None` when failed to load
+    static ref USER_FONTS: std::sync::Mutex<std::collections::HashMap<String, Option<ChainFont>>> =
+        std::sync::Mutex::new(std::collections::HashMap::new());
+}
+
+lazy_static::lazy_static! {
+    static ref FONT_DATA: std::sync::Mutex<std::collections::HashSet<&'static [u8]>> =
+        std::sync::Mutex::new(std::collections::HashSet::new());
+}
+
+/// Static font file for shaping face, same contents are leaked only once
+/// however many times user fonts are reloaded
+fn intern_font_data(data: Vec<u8>) -> &'static [u8] {
+    let mut interned = FONT_DATA.lock().unwrap();
+    match interned.get(data.as_slice()) {
+        Some(x) => x,
+        None => {
+            let leaked: &'static [u8] = Box::leak(data.into_boxed_slice());
+            interned.insert(leaked);
+            leaked
+        }
+    }
+}
+
+/// Folder for user TTF/OTF/TTC files
+pub(crate) fn user_fonts_folder() -> Option<std::path::PathBuf> {
+    crate::theme::user_folder("fonts")
+}
+
+/// File names of fonts in user fonts folder
+pub(crate) fn user_font_names() -> Vec<String> {
+    let Some(entries) = user_fonts_folder().and_then(|x| std::fs::read_dir(x).ok()) else {
+        return vec![];
+    };
+
+    let mut names = entries
+        .filter_map(|x| x.ok().map(|x| x.path()))
+        .filter(|x| {
+            x.extension().is_some_and(|ext| {
+                ["ttf", "otf", "ttc"]
+                    .iter()
+                    .any(|y| ext.eq_ignore_ascii_case(y))
+            })
+        })
+        .filter_map(|x| x.file_name().map(|x| x.to_string_lossy().into_owned()))
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+/// Forget loaded user fonts, so changed files are loaded again
+pub(crate) fn reload_user_fonts() {
+    USER_FONTS.lock().unwrap().clear();
+}
+
+fn load_user_font(name: &str) -> Option<ChainFont> {
+    // File name only, never path outside of fonts folder
+    if std::path::Path::new(name).file_name() != Some(std::ffi::OsStr::new(name)) {
+        log::error!("Invalid font name {name:?}");
+        return None;
+    }
+
+    USER_FONTS
+        .lock()
+        .unwrap()
+        .entry(name.to_owned())
+        .or_insert_with(|| {
+            let path = user_fonts_folder()?.join(name);
+            let loaded = std::fs::read(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|data| {
+                    // First face of collection for TTC, checked before data is interned
+                    ab_glyph::FontRef::try_from_slice_and_index(&data, 0)
+                        .map_err(|e| e.to_string())?;
+                    let data = intern_font_data(data);
+                    let font = ab_glyph::FontRef::try_from_slice_and_index(data, 0)
+                        .map_err(|e| e.to_string())?;
+                    ChainFont::new(ab_glyph::FontArc::new(font), data)
+                        .ok_or_else(|| "cannot parse font for shaping".to_owned())
+                });
+
+            match loaded {
+                Ok(font) => Some(font),
+                Err(e) => {
+                    log::error!("Failed to load font {}: {e}", path.display());
+                    None
+                }
+            }
+        })
+        .clone()
+}
+
+/// Bundled font or file name in user fonts folder.
+/// Serialized as "sans", "digits", "mono" or file name like "MyFont.otf"
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum FontChoice {
+    Bundled(OverlayFont),
+    User(String),
+}
+
+impl From<String> for FontChoice {
+    fn from(value: String) -> Self {
+        use strum::IntoEnumIterator;
+
+        OverlayFont::iter()
+            .find(|x| x.key() == value)
+            .map(Self::Bundled)
+            .unwrap_or(Self::User(value))
+    }
+}
+
+impl From<FontChoice> for String {
+    fn from(value: FontChoice) -> Self {
+        match value {
+            FontChoice::Bundled(x) => x.key().to_owned(),
+            FontChoice::User(x) => x,
+        }
+    }
+}
+
+impl FontChoice {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Bundled(x) => x.label().to_owned(),
+            Self::User(x) => x.clone(),
+        }
+    }
+
+    /// `None` when user font is missing or failed to load
+    pub fn try_chain(&self) -> Option<FontChain> {
+        let primary = match self {
+            Self::Bundled(x) => x.chain_font(),
+            Self::User(name) => load_user_font(name)?,
+        };
+        Some(FontChain::new(primary))
+    }
+
+    /// Missing user font falls back to Noto Sans KR
+    pub fn chain(&self) -> FontChain {
+        self.try_chain()
+            .unwrap_or_else(|| FontChain::new(OverlayFont::Sans.chain_font()))
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui, id_salt: impl std::hash::Hash) {
+        use strum::IntoEnumIterator;
+
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(self.label())
+            .show_ui(ui, |ui| {
+                for font in OverlayFont::iter() {
+                    ui.selectable_value(self, Self::Bundled(font), font.label());
+                }
+                let user_fonts = user_font_names();
+                if !user_fonts.is_empty() {
+                    ui.separator();
+                }
+                for name in user_fonts {
+                    ui.selectable_value(self, Self::User(name.clone()), name);
+                }
+            })
+            .response
+            .on_hover_text(rust_i18n::t!(
+                "theme.font_description",
+                path = user_fonts_folder()
+                    .map(|x| x.display().to_string())
+                    .unwrap_or_default()
+            ));
+    }
 }
 
 // Demonstrates how to replace all fonts.
@@ -116,6 +116,25 @@ impl OriginalExif {
     pub fn datetime(&self) -> String {
         self.get_exif_value(Tag::DateTime)
     }
+
+    /// Capture time with sub-second digits, editors rewrite `datetime` but keep this
+    pub fn datetime_original(&self) -> String {
+        format!(
+            "{}{}",
+            self.get_exif_value(Tag::DateTimeOriginal),
+            self.get_exif_value(Tag::SubSecTimeOriginal)
+        )
+    }
+
+    /// Camera owner, photographer or image creator
+    pub fn artist(&self) -> String {
+        self.get_exif_trim_string(Tag::Artist)
+    }
+
+    /// Copyright holder
+    pub fn copyright(&self) -> String {
+        self.get_exif_trim_string(Tag::Copyright)
+    }
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
@@ -129,6 +148,8 @@ pub struct SimplifiedExif {
     pub exposure: String,
     pub iso_speed: Option<u32>,
     pub datetime: String, // Option<DateTime>,
+    pub artist: String,
+    pub copyright: String,
 
     #[serde(skip)]
     pub orientation: image::metadata::Orientation,
@@ -145,11 +166,24 @@ impl core::default::Default for SimplifiedExif {
             exposure: String::new(),
             iso_speed: None,
             datetime: String::new(),
+            artist: String::new(),
+            copyright: String::new(),
             orientation: image::metadata::Orientation::NoTransforms,
         }
     }
 }
 
+/// Date and time parts parsed from EXIF datetime string
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ExifDateTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
 /// Remove trash chars from exif string field
 fn simplify_exif_string(input: &str) -> String {
     let mut parts = Vec::new();
@@ -191,6 +225,8 @@ impl From<&OriginalExif> for SimplifiedExif {
             exposure: value.exposure(),
             iso_speed: value.iso_speed(),
             datetime: value.datetime(),
+            artist: value.artist(),
+            copyright: value.copyright(),
             orientation: value.orientation(),
         }
     }
@@ -262,10 +298,63 @@ impl SimplifiedExif {
         }
     }
 
+    pub fn get_focal(&self) -> Option<String> {
+        match self.focal.as_str() {
+            "" | "0" | "0.0" | "0.00" => None,
+            others => Some(others.to_string()),
+        }
+    }
+
     pub fn get_iso(&self) -> Option<String> {
         self.iso_speed.map(|x| x.to_string())
     }
 
+    /// Parse "2025-10-17 12:34:56" (or raw "2025:10:17 12:34:56"), time is optional
+    pub fn get_datetime(&self) -> Option<ExifDateTime> {
+        let mut nums = self
+            .datetime
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|x| !x.is_empty());
+        let mut next = || nums.next().and_then(|x| x.parse::<u16>().ok());
+
+        let (year, month, day) = (next()?, next()?, next()?);
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+
+        let (hour, minute, second) = (
+            next().unwrap_or(0),
+            next().unwrap_or(0),
+            next().unwrap_or(0),
+        );
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+
+        Some(ExifDateTime {
+            year,
+            month: month as u8,
+            day: day as u8,
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+        })
+    }
+
+    /// "35mm  f/2.8  1/250s  ISO100", skip missing values
+    pub fn settings_summary(&self) -> String {
+        [
+            self.get_focal().map(|x| format!("{x}mm")),
+            self.get_fnumber().map(|x| format!("f/{x}")),
+            self.get_exposure().map(|x| format!("{x}s")),
+            self.get_iso().map(|x| format!("ISO{x}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("  ")
+    }
+
     pub fn update_ui(&mut self, ui: &mut egui::Ui, editable: bool) {
         let small_text = |text: &str| RichText::new(text).text_style(TextStyle::Small);
 
@@ -391,6 +480,35 @@ impl SimplifiedExif {
         }
 
         ui.end_row();
+
+        // Artist and copyright, only when exist or editable
+        if editable || !self.artist.is_empty() {
+            ui.label(small_text("Artist"));
+            if editable {
+                ui.add(
+                    TextEdit::singleline(&mut self.artist)
+                        .font(TextStyle::Small)
+                        .desired_width(140.0),
+                );
+            } else {
+                ui.label(small_text(&self.artist));
+            }
+            ui.end_row();
+        }
+
+        if editable || !self.copyright.is_empty() {
+            ui.label(small_text("Copyright"));
+            if editable {
+                ui.add(
+                    TextEdit::singleline(&mut self.copyright)
+                        .font(TextStyle::Small)
+                        .desired_width(140.0),
+                );
+            } else {
+                ui.label(small_text(&self.copyright));
+            }
+            ui.end_row();
+        }
     }
 
     pub fn is_vertical_rotated(&self) -> bool {
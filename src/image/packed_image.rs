@@ -46,6 +46,18 @@ pub struct PackedImage {
     /// editable button for UI
     pub editable: bool,
 
+    /// position in image list starting from 1, frame number of film themes
+    pub sequence: usize,
+
+    /// included in contact sheet
+    pub on_contact_sheet: bool,
+
+    /// where to keep when theme crops, percent of cropped space (-100 ~ 100) on x and y
+    pub crop_offset: [f32; 2],
+
+    /// own adjustments instead of global adjustments of export config
+    pub develop: Option<crate::develop::Develop>,
+
     /// texture internally for egui framework
     pub texture: egui::TextureHandle,
 }
@@ -192,6 +204,7 @@ impl PackedImage {
     pub fn with_scale_and_orientation(
         &self,
         scale: crate::scale_config::ScaleConfig,
+        sharpen: crate::export_config::sharpen::Sharpen,
     ) -> Result<image::DynamicImage, image::ImageError> {
         use image::ImageBuffer;
         use image::Rgba;
@@ -204,9 +217,13 @@ impl PackedImage {
 
         let resized_image: fast_image_resize::images::Image<'static> =
             resize_image(dyn_image, new_width, new_height)?;
-        let buffer =
+        let mut buffer =
             ImageBuffer::<Rgba<u8>, _>::from_raw(new_width, new_height, resized_image.into_vec())
                 .expect("Failed to convert to ImageBuffer");
+        // Downscale softens detail, sharpen for output
+        if new_width < old_width {
+            sharpen.apply(&mut buffer);
+        }
 
         let mut dyn_image = image::DynamicImage::ImageRgba8(buffer);
         dyn_image.apply_orientation(orientation);
@@ -214,6 +231,60 @@ impl PackedImage {
         Ok(dyn_image)
     }
 
+    /// Adjustments of this image, own one or global one
+    pub fn develop_settings<'a>(
+        &'a self,
+        export_config: &'a crate::export_config::ExportConfig,
+    ) -> &'a crate::develop::Develop {
+        self.develop
+            .as_ref()
+            .unwrap_or(&export_config.develop.global)
+    }
+
+    /// Fixed per image, so grain does not change between exports or after moving folder.
+    /// Capture time tells same named photos from two cards apart
+    pub fn seed(&self) -> u64 {
+        let key = format!(
+            "{}\n{}",
+            self.file_name(),
+            self.src_exif.datetime_original()
+        );
+        crate::develop::grain::fnv1a(key.as_bytes())
+    }
+
+    /// Apply adjustments of this image on already scaled and oriented image
+    pub fn apply_develop(
+        &self,
+        export_config: &crate::export_config::ExportConfig,
+        image: &mut image::RgbaImage,
+    ) {
+        self.develop_settings(export_config)
+            .apply(image, self.seed());
+    }
+
+    /// Scaled and oriented image with adjustments applied, input of every theme
+    pub fn developed(
+        &self,
+        export_config: &crate::export_config::ExportConfig,
+    ) -> Result<image::DynamicImage, image::ImageError> {
+        self.developed_with_crop(export_config, |image| image)
+    }
+
+    /// Same as `developed` for theme cropping photo, crop comes before adjustments,
+    /// so vignette and light leak follow edge of visible frame
+    pub fn developed_with_crop(
+        &self,
+        export_config: &crate::export_config::ExportConfig,
+        crop: impl FnOnce(image::RgbaImage) -> image::RgbaImage,
+    ) -> Result<image::DynamicImage, image::ImageError> {
+        let mut image = crop(
+            self.with_scale_and_orientation(export_config.scale_config, export_config.sharpen)?
+                .into_rgba8(),
+        );
+        self.apply_develop(export_config, &mut image);
+        Ok(image::DynamicImage::ImageRgba8(image))
+    }
+
     pub fn try_from_path(path: &PathBuf, ctx: &egui::Context) -> Result<Self, image::ImageError> {
         let file = std::fs::File::open(path)?;
         let mut buf_reader = std::io::BufReader::new(file);
@@ -250,6 +321,10 @@ impl PackedImage {
             src_exif: original_exif,
             view_exif,
             editable: false,
+            sequence: 1,
+            on_contact_sheet: true,
+            crop_offset: [0.0, 0.0],
+            develop: None,
             texture: ctx.load_texture(file_name, thumbnail, egui::TextureOptions::NEAREST),
         })
     }
@@ -325,6 +400,10 @@ impl PackedImage {
                     ui.horizontal(|ui| {
                         ui.label(self.file_name());
                         self.update_editable_button(ui);
+                        ui.checkbox(
+                            &mut self.on_contact_sheet,
+                            t!("app.images.on_contact_sheet"),
+                        );
                     });
 
                     ui.scope_builder(ui_builder, |ui| {
@@ -337,6 +416,29 @@ impl PackedImage {
                             })
                     });
 
+                    if self.editable {
+                        ui.horizontal(|ui| {
+                            ui.label(t!("app.images.crop_offset"))
+                                .on_hover_text(t!("app.images.crop_offset_description"));
+                            for value in self.crop_offset.iter_mut() {
+                                ui.add(egui::Slider::new(value, -100.0..=100.0).suffix("%"));
+                            }
+                        });
+
+                        let mut own_develop = self.develop.is_some();
+                        if ui
+                            .checkbox(&mut own_develop, t!("app.images.own_develop"))
+                            .on_hover_text(t!("app.images.own_develop_description"))
+                            .changed()
+                        {
+                            self.develop =
+                                own_develop.then(|| export_config.develop.global.clone());
+                        }
+                        if let Some(develop) = &mut self.develop {
+                            develop.update_ui(ui, &self.path);
+                        }
+                    }
+
                     if !self.editable {
                         ui.horizontal(|ui| {
                             ui.horizontal(|ui| {
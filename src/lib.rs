@@ -8,6 +8,7 @@
 rust_i18n::i18n!("locales");
 
 mod app;
+pub(crate) mod develop;
 pub(crate) mod export_config;
 pub(crate) mod fonts;
 // pub(crate) use fonts::builtin_fonts;
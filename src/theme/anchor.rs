@@ -0,0 +1,85 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Nine point anchor to place overlay on canvas
+
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+#[derive(EnumIter, Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    pub fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::TopLeft => t!("theme.anchor.top_left"),
+            Self::Top => t!("theme.anchor.top"),
+            Self::TopRight => t!("theme.anchor.top_right"),
+            Self::Left => t!("theme.anchor.left"),
+            Self::Center => t!("theme.anchor.center"),
+            Self::Right => t!("theme.anchor.right"),
+            Self::BottomLeft => t!("theme.anchor.bottom_left"),
+            Self::Bottom => t!("theme.anchor.bottom"),
+            Self::BottomRight => t!("theme.anchor.bottom_right"),
+        }
+    }
+
+    /// Horizontal factor, 0.0 left / 0.5 center / 1.0 right
+    pub fn fx(&self) -> f32 {
+        match self {
+            Self::TopLeft | Self::Left | Self::BottomLeft => 0.0,
+            Self::Top | Self::Center | Self::Bottom => 0.5,
+            Self::TopRight | Self::Right | Self::BottomRight => 1.0,
+        }
+    }
+
+    /// Vertical factor, 0.0 top / 0.5 center / 1.0 bottom
+    pub fn fy(&self) -> f32 {
+        match self {
+            Self::TopLeft | Self::Top | Self::TopRight => 0.0,
+            Self::Left | Self::Center | Self::Right => 0.5,
+            Self::BottomLeft | Self::Bottom | Self::BottomRight => 1.0,
+        }
+    }
+
+    /// Top-left position of item which size is `item_w` x `item_h`,
+    /// `margin` is ignored on centered axis
+    pub fn position(
+        &self,
+        canvas_w: f32,
+        canvas_h: f32,
+        item_w: f32,
+        item_h: f32,
+        margin: f32,
+    ) -> (f32, f32) {
+        let (fx, fy) = (self.fx(), self.fy());
+        let x = margin + (canvas_w - item_w - margin * 2.0) * fx;
+        let y = margin + (canvas_h - item_h - margin * 2.0) * fy;
+        (x, y)
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui, id_salt: impl std::hash::Hash) {
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(self.label())
+            .show_ui(ui, |ui| {
+                for anchor in Anchor::iter() {
+                    ui.selectable_value(self, anchor, anchor.label());
+                }
+            });
+    }
+}
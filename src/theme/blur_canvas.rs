@@ -0,0 +1,291 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Fit photo into fixed aspect canvas over blurred and darkened enlargement of itself.
+//! Mostly for square-feed platforms.
+
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::theme::{Theme, effects, template, text_dimensions, text_effects::TextEffects};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+const DEFAULT_FONT_SIZE: u32 = 25;
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum CanvasAspect {
+    /// 1:1
+    Square,
+    /// 4:5
+    Portrait,
+    /// 9:16
+    Story,
+    /// 16:9
+    Wide,
+    /// 4:3
+    Classic,
+}
+
+impl CanvasAspect {
+    pub(crate) fn label(&self) -> &str {
+        match self {
+            Self::Square => "1:1",
+            Self::Portrait => "4:5",
+            Self::Story => "9:16",
+            Self::Wide => "16:9",
+            Self::Classic => "4:3",
+        }
+    }
+
+    /// width / height
+    pub(crate) fn ratio(&self) -> f32 {
+        match self {
+            Self::Square => 1.0,
+            Self::Portrait => 4.0 / 5.0,
+            Self::Story => 9.0 / 16.0,
+            Self::Wide => 16.0 / 9.0,
+            Self::Classic => 4.0 / 3.0,
+        }
+    }
+
+    /// Canvas size to contain photo with margin on this aspect ratio,
+    /// `margin` is percent of long edge of photo
+    pub(crate) fn canvas_size(&self, img_w: u32, img_h: u32, margin: f32) -> (u32, u32) {
+        let ratio = self.ratio();
+        let margin = img_w.max(img_h) as f32 * margin / 100.0;
+        let (min_w, min_h) = (img_w as f32 + margin * 2.0, img_h as f32 + margin * 2.0);
+
+        if min_w / min_h > ratio {
+            (min_w.round() as u32, (min_w / ratio).round() as u32)
+        } else {
+            ((min_h * ratio).round() as u32, min_h.round() as u32)
+        }
+    }
+
+    pub(crate) fn update_ui(&mut self, ui: &mut egui::Ui, id_salt: impl std::hash::Hash) {
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(self.label())
+            .show_ui(ui, |ui| {
+                for aspect in CanvasAspect::iter() {
+                    ui.selectable_value(self, aspect, aspect.label());
+                }
+            });
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct BlurCanvas {
+    aspect: CanvasAspect,
+    /// Minimum margin around photo in percent of its long edge
+    margin: f32,
+    /// Background blur in percent of canvas short edge
+    blur: f32,
+    /// Background brightness in percent
+    brightness: f32,
+    /// Corner radius in percent of photo short edge, zero to disable
+    corner_radius: f32,
+    shadow: bool,
+    /// Shadow blur in percent of photo short edge
+    shadow_blur: f32,
+    show_exif: bool,
+    font_color: egui::Color32,
+    font: FontChoice,
+    font_size: f32,
+    /// Shooting settings text, see `template`
+    settings: String,
+    effects: TextEffects,
+}
+
+impl core::default::Default for BlurCanvas {
+    fn default() -> Self {
+        Self {
+            aspect: CanvasAspect::Square,
+            margin: 6.0,
+            blur: 4.0,
+            brightness: 60.0,
+            corner_radius: 0.0,
+            shadow: true,
+            shadow_blur: 2.0,
+            show_exif: false,
+            font_color: egui::Color32::WHITE,
+            font: FontChoice::Bundled(OverlayFont::Sans),
+            font_size: DEFAULT_FONT_SIZE as f32,
+            settings: template::DEFAULT_SETTINGS.to_owned(),
+            effects: TextEffects::default(),
+        }
+    }
+}
+
+impl BlurCanvas {
+    /// relative size on canvas width
+    fn rel_size<F: Copy + num_traits::AsPrimitive<f32>>(&self, size: F, canvas_w: u32) -> f32 {
+        size.as_() * (self.font_size / (DEFAULT_FONT_SIZE as f32)) * (canvas_w as f32 / 1000.0)
+    }
+}
+
+impl Theme for BlurCanvas {
+    fn unique_name(&self) -> &'static str {
+        "blur_canvas"
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        t!("theme.blur_canvas")
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let exif = &pi.view_exif;
+        let mut photo = pi.developed(export_config)?.to_rgba8();
+        let (img_w, img_h) = photo.dimensions();
+        let (canvas_w, canvas_h) = self.aspect.canvas_size(img_w, img_h, self.margin);
+        let (photo_x, photo_y) = ((canvas_w - img_w) / 2, (canvas_h - img_h) / 2);
+
+        let mut canvas = effects::blurred_cover(
+            &photo,
+            canvas_w,
+            canvas_h,
+            canvas_w.min(canvas_h) as f32 * self.blur / 100.0,
+            self.brightness / 100.0,
+        );
+
+        let short = img_w.min(img_h) as f32;
+        let mask = effects::rounded_rect_mask(img_w, img_h, short * self.corner_radius / 100.0);
+
+        if self.shadow {
+            let (shadow, padding) = effects::soft_shadow(&mask, short * self.shadow_blur / 100.0);
+            let offset = (short * 0.01).round() as i64;
+            crate::theme::blend_mask_mut(
+                &mut canvas,
+                &shadow,
+                photo_x as i64 - padding as i64 + offset,
+                photo_y as i64 - padding as i64 + offset,
+                image::Rgba([0, 0, 0, 160]),
+            );
+        }
+
+        effects::apply_mask_alpha(&mut photo, &mask);
+        image::imageops::overlay(&mut canvas, &photo, photo_x as i64, photo_y as i64);
+
+        // EXIF text on bottom padding
+        if self.show_exif {
+            let font = self.font.chain();
+            let color = crate::theme::color32_to_rgba(self.font_color);
+            let main_scale = ab_glyph::PxScale::from(self.rel_size(30, canvas_w));
+            let sub_scale = ab_glyph::PxScale::from(self.rel_size(22, canvas_w));
+
+            let camera = format!("{}  {}", exif.camera_mnf, exif.camera_model)
+                .trim()
+                .to_owned();
+            let settings = template::render(&self.settings, exif).trim().to_owned();
+            let (camera_w, camera_h) = text_dimensions(main_scale, &font, &camera);
+            let (settings_w, settings_h) = text_dimensions(sub_scale, &font, &settings);
+
+            let padding_top = (photo_y + img_h) as f32;
+            let padding_h = (canvas_h - photo_y - img_h) as f32;
+            let y = padding_top + (padding_h - camera_h - settings_h) / 2.0;
+
+            let (camera_x, settings_x) = (
+                (canvas_w as f32 - camera_w) / 2.0,
+                (canvas_w as f32 - settings_w) / 2.0,
+            );
+            self.effects.draw_plate(
+                &mut canvas,
+                camera_x.min(settings_x),
+                y,
+                camera_w.max(settings_w),
+                camera_h + settings_h,
+                main_scale.y,
+            );
+            self.effects
+                .draw_text(&mut canvas, &font, main_scale, camera_x, y, color, &camera);
+            self.effects.draw_text(
+                &mut canvas,
+                &font,
+                sub_scale,
+                settings_x,
+                y + camera_h,
+                color,
+                &settings,
+            );
+        }
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.blur_canvas_config.aspect"));
+                self.aspect.update_ui(ui, "blur_canvas_aspect");
+            });
+            ui.add(
+                egui::Slider::new(&mut self.margin, 0.0..=20.0)
+                    .suffix("%")
+                    .text(t!("theme.blur_canvas_config.margin")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.blur, 0.0..=10.0)
+                    .suffix("%")
+                    .text(t!("theme.blur_canvas_config.blur")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.brightness, 0.0..=100.0)
+                    .suffix("%")
+                    .text(t!("theme.blur_canvas_config.brightness")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.corner_radius, 0.0..=20.0)
+                    .suffix("%")
+                    .text(t!("theme.blur_canvas_config.corner_radius")),
+            );
+
+            ui.checkbox(&mut self.shadow, t!("theme.blur_canvas_config.shadow"));
+            ui.add_enabled(
+                self.shadow,
+                egui::Slider::new(&mut self.shadow_blur, 0.0..=10.0)
+                    .suffix("%")
+                    .text(t!("theme.blur_canvas_config.shadow_blur")),
+            );
+
+            ui.checkbox(
+                &mut self.show_exif,
+                t!("theme.blur_canvas_config.show_exif"),
+            );
+            ui.add_enabled_ui(self.show_exif, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme.settings_template"));
+                    template::update_ui(ui, &mut self.settings);
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme.font"));
+                    self.font.update_ui(ui, "blur_canvas_font");
+                });
+                ui.add(
+                    egui::Slider::new(&mut self.font_size, 1.0..=100.0).text(t!("theme.font_size")),
+                )
+                .on_hover_text(t!(
+                    "theme.font_size_description",
+                    default = DEFAULT_FONT_SIZE
+                ));
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme.font_color"));
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.font_color,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                });
+                self.effects.update_ui(ui, "blur_canvas_text_effects");
+            });
+        });
+    }
+}
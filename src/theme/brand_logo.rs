@@ -0,0 +1,75 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Bundled camera maker logos
+//!
+//! Logos are white alpha masks (PNG, LumaA8) and get tinted on drawing.
+//! Current files are plain wordmarks, replace PNG under `assets/logos`
+//! to use official artwork without touching code.
+
+pub(crate) struct BrandLogo {
+    pub(crate) name: &'static str,
+    /// lowercase keywords to find on EXIF `Make` field
+    keywords: &'static [&'static str],
+    data: &'static [u8],
+}
+
+macro_rules! brand_logo {
+    ($name:expr, $keywords:expr, $file:expr) => {
+        BrandLogo {
+            name: $name,
+            keywords: $keywords,
+            data: include_bytes!(concat!("../../assets/logos/", $file)),
+        }
+    };
+}
+
+#[rustfmt::skip]
+const BRAND_LOGOS: [BrandLogo; 12] = [
+    brand_logo!("Sony", &["sony"], "sony.png"),
+    brand_logo!("Fujifilm", &["fujifilm", "fuji photo"], "fujifilm.png"),
+    brand_logo!("Nikon", &["nikon"], "nikon.png"),
+    brand_logo!("Canon", &["canon"], "canon.png"),
+    brand_logo!("Leica", &["leica"], "leica.png"),
+    brand_logo!("Panasonic", &["panasonic"], "panasonic.png"),
+    brand_logo!("OM System", &["om digital", "om system"], "om_system.png"),
+    brand_logo!("Olympus", &["olympus"], "olympus.png"),
+    brand_logo!("Pentax", &["pentax"], "pentax.png"),
+    brand_logo!("Ricoh", &["ricoh"], "ricoh.png"),
+    brand_logo!("Hasselblad", &["hasselblad"], "hasselblad.png"),
+    brand_logo!("Sigma", &["sigma"], "sigma.png"),
+];
+
+impl BrandLogo {
+    /// Find logo by EXIF `Make` field such as "NIKON CORPORATION" or "FUJIFILM"
+    pub(crate) fn find(camera_mnf: &str) -> Option<&'static Self> {
+        let mnf = camera_mnf.to_lowercase();
+
+        BRAND_LOGOS
+            .iter()
+            .find(|logo| logo.keywords.iter().any(|k| mnf.contains(k)))
+    }
+
+    /// Decode logo as coverage mask and resize it to given height with aspect ratio
+    pub(crate) fn mask(&self, height: u32) -> Result<image::GrayImage, image::ImageError> {
+        let logo = image::load_from_memory_with_format(self.data, image::ImageFormat::Png)?
+            .to_luma_alpha8();
+        let mask = image::GrayImage::from_fn(logo.width(), logo.height(), |x, y| {
+            image::Luma([logo.get_pixel(x, y)[1]])
+        });
+        let height = height.max(1);
+        let width = ((mask.width() as f32 * height as f32) / mask.height() as f32)
+            .round()
+            .max(1.0) as u32;
+
+        Ok(image::imageops::resize(
+            &mask,
+            width,
+            height,
+            image::imageops::FilterType::Lanczos3,
+        ))
+    }
+}
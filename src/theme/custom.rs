@@ -0,0 +1,387 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! User defined theme from JSON file in themes folder.
+//! Every length is percent of short edge of photo, so the look is same on any `ScaleConfig`.
+//! Padding is up to 400 on each side and `size` of element up to 100.
+//!
+//! ```json
+//! {
+//!   "name": "polaroid",
+//!   "label": "Polaroid",
+//!   "canvas": {
+//!     "padding": { "top": 5, "right": 5, "bottom": 22, "left": 5 },
+//!     "background": "#f4f1ea"
+//!   },
+//!   "elements": [
+//!     {
+//!       "type": "text", "region": "bottom", "anchor": "center",
+//!       "font": "sans", "size": 3.5, "color": "#202020",
+//!       "template": "{camera_mnf} {camera_model}\n{settings}",
+//!       "effects": { "shadow": true, "shadow_color": "#000000a0" }
+//!     },
+//!     {
+//!       "type": "image", "region": "bottom", "anchor": "right", "offset": [4, 0],
+//!       "path": "logo.svg", "size": 6, "opacity": 80
+//!     }
+//!   ]
+//! }
+//! ```
+
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::theme::script::{MAX_CANVAS_PIXELS, MAX_PAD_RATIO};
+use crate::theme::{
+    HexColor, Theme, anchor::Anchor, template, text_dimensions, text_effects::TextEffects,
+};
+use rust_i18n::t;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Prefix of `unique_name` to distinguish from built-in themes
+pub const UNIQUE_NAME_PREFIX: &str = "custom:";
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct Padding {
+    top: f32,
+    right: f32,
+    bottom: f32,
+    left: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CanvasSpec {
+    padding: Padding,
+    background: HexColor,
+}
+
+impl core::default::Default for CanvasSpec {
+    fn default() -> Self {
+        Self {
+            padding: Padding::default(),
+            background: HexColor(image::Rgba([255, 255, 255, 255])),
+        }
+    }
+}
+
+/// Area where element is anchored
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+enum Region {
+    /// Whole output
+    #[default]
+    Canvas,
+    /// Photo only
+    Photo,
+    /// Padding above photo
+    Top,
+    /// Padding below photo
+    Bottom,
+    /// Padding on left of photo
+    Left,
+    /// Padding on right of photo
+    Right,
+}
+
+fn default_size() -> f32 {
+    3.0
+}
+
+fn default_opacity() -> f32 {
+    100.0
+}
+
+fn default_font() -> FontChoice {
+    FontChoice::Bundled(OverlayFont::Sans)
+}
+
+fn default_text_color() -> HexColor {
+    HexColor(image::Rgba([0, 0, 0, 255]))
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum Element {
+    Text {
+        #[serde(default)]
+        region: Region,
+        anchor: Anchor,
+        /// Move toward inside of region, percent of short edge
+        #[serde(default)]
+        offset: [f32; 2],
+        #[serde(default = "default_font")]
+        /// "sans", "digits", "mono" or file name in fonts folder
+        font: FontChoice,
+        /// Line height, percent of short edge
+        #[serde(default = "default_size")]
+        size: f32,
+        #[serde(default = "default_text_color")]
+        color: HexColor,
+        template: String,
+        /// Outline, shadow and plate, same fields as built-in themes
+        #[serde(default)]
+        effects: TextEffects,
+    },
+    Image {
+        #[serde(default)]
+        region: Region,
+        anchor: Anchor,
+        #[serde(default)]
+        offset: [f32; 2],
+        /// Relative to theme file
+        path: PathBuf,
+        /// Image height, percent of short edge
+        #[serde(default = "default_size")]
+        size: f32,
+        #[serde(default = "default_opacity")]
+        opacity: f32,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CustomThemeSpec {
+    name: String,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    canvas: CanvasSpec,
+    #[serde(default)]
+    elements: Vec<Element>,
+}
+
+pub struct CustomTheme {
+    /// `Theme::unique_name` requires static lifetime
+    unique_name: &'static str,
+    label: String,
+    source: PathBuf,
+    spec: CustomThemeSpec,
+}
+
+impl CustomTheme {
+    /// Load and validate theme file, error message contains path and reason
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let error = |reason: String| format!("{}: {reason}", path.display());
+
+        let text = std::fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+        let mut spec: CustomThemeSpec =
+            serde_json::from_str(&text).map_err(|e| error(e.to_string()))?;
+
+        if spec.name.trim().is_empty() {
+            return Err(error("\"name\" is empty".to_owned()));
+        }
+
+        // Same limit as `pad()` of script themes, in percent of short edge
+        let max_padding = MAX_PAD_RATIO * 100.0;
+        let padding = &spec.canvas.padding;
+        if [padding.top, padding.right, padding.bottom, padding.left]
+            .iter()
+            .any(|x| !(0.0..=max_padding).contains(x))
+        {
+            return Err(error(format!(
+                "canvas padding must be between 0 and {max_padding}"
+            )));
+        }
+
+        let base = path.parent().unwrap_or(Path::new("."));
+        for (idx, element) in spec.elements.iter_mut().enumerate() {
+            let (Element::Text { size, .. } | Element::Image { size, .. }) = element;
+            if !(0.0..=100.0).contains(size) {
+                return Err(error(format!(
+                    "elements[{idx}] size must be between 0 and 100"
+                )));
+            }
+
+            match element {
+                Element::Text { template, .. } => {
+                    template::validate(template)
+                        .map_err(|e| error(format!("elements[{idx}] {e}")))?;
+                }
+                Element::Image { path, .. } => {
+                    if path.is_relative() {
+                        *path = base.join(&*path);
+                    }
+                    if !path.is_file() {
+                        return Err(error(format!(
+                            "elements[{idx}] image {} not found",
+                            path.display()
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            unique_name: crate::theme::intern(format!("{UNIQUE_NAME_PREFIX}{}", spec.name)),
+            label: spec.label.clone().unwrap_or_else(|| spec.name.clone()),
+            source: path.to_path_buf(),
+            spec,
+        })
+    }
+}
+
+/// (x, y, w, h)
+type Rect = (f32, f32, f32, f32);
+
+/// Top-left position of item inside of region, offset moves toward inside
+fn place(anchor: Anchor, region: Rect, item_w: f32, item_h: f32, offset: [f32; 2]) -> (f32, f32) {
+    let (rx, ry, rw, rh) = region;
+    let (x, y) = anchor.position(rw, rh, item_w, item_h, 0.0);
+    let dx = if anchor.fx() > 0.5 {
+        -offset[0]
+    } else {
+        offset[0]
+    };
+    let dy = if anchor.fy() > 0.5 {
+        -offset[1]
+    } else {
+        offset[1]
+    };
+    (rx + x + dx, ry + y + dy)
+}
+
+impl Theme for CustomTheme {
+    fn unique_name(&self) -> &'static str {
+        self.unique_name
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        std::borrow::Cow::Owned(self.label.clone())
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let photo = pi.developed(export_config)?.to_rgba8();
+        let (img_w, img_h) = photo.dimensions();
+        let unit = img_w.min(img_h) as f32 / 100.0;
+
+        let padding = &self.spec.canvas.padding;
+        let [top, right, bottom, left] = [padding.top, padding.right, padding.bottom, padding.left]
+            .map(|x| (x.max(0.0) * unit).round() as u32);
+        let (canvas_w, canvas_h) = (left + img_w + right, top + img_h + bottom);
+        if canvas_w as u64 * canvas_h as u64 > MAX_CANVAS_PIXELS {
+            return Err(image::ImageError::Parameter(
+                image::error::ParameterError::from_kind(image::error::ParameterErrorKind::Generic(
+                    format!(
+                        "{}: canvas {canvas_w}x{canvas_h} is too large, up to {MAX_CANVAS_PIXELS} pixels",
+                        self.source.display()
+                    ),
+                )),
+            ));
+        }
+
+        let mut canvas =
+            image::RgbaImage::from_pixel(canvas_w, canvas_h, self.spec.canvas.background.0);
+        image::imageops::overlay(&mut canvas, &photo, left as i64, top as i64);
+
+        let region_rect = |region: Region| -> Rect {
+            let (l, t, w, h) = (left as f32, top as f32, img_w as f32, img_h as f32);
+            match region {
+                Region::Canvas => (0.0, 0.0, canvas_w as f32, canvas_h as f32),
+                Region::Photo => (l, t, w, h),
+                Region::Top => (0.0, 0.0, canvas_w as f32, t),
+                Region::Bottom => (0.0, t + h, canvas_w as f32, bottom as f32),
+                Region::Left => (0.0, t, l, h),
+                Region::Right => (l + w, t, right as f32, h),
+            }
+        };
+
+        for element in &self.spec.elements {
+            match element {
+                Element::Text {
+                    region,
+                    anchor,
+                    offset,
+                    font,
+                    size,
+                    color,
+                    template,
+                    effects,
+                } => {
+                    let text = template::render(template, &pi.view_exif);
+                    let lines = text
+                        .lines()
+                        .map(str::trim_end)
+                        .filter(|x| !x.is_empty())
+                        .collect::<Vec<_>>();
+                    if lines.is_empty() {
+                        continue;
+                    }
+
+                    let font = font.chain();
+                    let scale = ab_glyph::PxScale::from((size * unit).max(1.0));
+                    let sizes = lines
+                        .iter()
+                        .map(|line| text_dimensions(scale, &font, line))
+                        .collect::<Vec<_>>();
+                    let block_w = sizes.iter().map(|(w, _)| *w).fold(0.0, f32::max);
+                    let block_h = sizes.iter().map(|(_, h)| *h).sum::<f32>();
+
+                    let offset = offset.map(|x| x * unit);
+                    let (x, mut y) = place(*anchor, region_rect(*region), block_w, block_h, offset);
+                    effects.draw_plate(&mut canvas, x, y, block_w, block_h, scale.y);
+
+                    for (line, (line_w, line_h)) in lines.iter().zip(sizes) {
+                        // Align each line in block along with horizontal anchor
+                        let line_x = x + (block_w - line_w) * anchor.fx();
+                        effects.draw_text(&mut canvas, &font, scale, line_x, y, color.0, line);
+                        y += line_h;
+                    }
+                }
+                Element::Image {
+                    region,
+                    anchor,
+                    offset,
+                    path,
+                    size,
+                    opacity,
+                } => {
+                    let height = (size * unit).round().max(1.0) as u32;
+                    let mut stamp = crate::theme::load_image_with_height(path, height)?;
+
+                    let opacity = opacity.clamp(0.0, 100.0) / 100.0;
+                    for px in stamp.pixels_mut() {
+                        px[3] = (px[3] as f32 * opacity).round() as u8;
+                    }
+
+                    let offset = offset.map(|x| x * unit);
+                    let (x, y) = place(
+                        *anchor,
+                        region_rect(*region),
+                        stamp.width() as f32,
+                        stamp.height() as f32,
+                        offset,
+                    );
+                    image::imageops::overlay(
+                        &mut canvas,
+                        &stamp,
+                        x.round() as i64,
+                        y.round() as i64,
+                    );
+                }
+            }
+        }
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.label(t!(
+                "theme.custom_config.source",
+                path = self.source.display().to_string()
+            ));
+            ui.label(t!("theme.custom_config.reload_hint"));
+        });
+    }
+}
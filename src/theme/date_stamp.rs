@@ -0,0 +1,213 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Orange date imprint on lower right, like point-and-shoot film cameras
+
+use crate::exif_impl::ExifDateTime;
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::theme::{Theme, film::FILM_COLOR, text_dimensions, text_effects::TextEffects};
+use imageproc::integral_image::ArrayData;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+const DEFAULT_FONT_SIZE: u32 = 25;
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum DateFormat {
+    /// '25 10 17
+    YearMonthDay,
+    /// 10 17 '25
+    MonthDayYear,
+    /// 17 10 '25
+    DayMonthYear,
+    /// 2025 10 17
+    FullYearMonthDay,
+    /// '25 10 17  12:34
+    YearMonthDayTime,
+}
+
+impl DateFormat {
+    fn format(&self, dt: &ExifDateTime) -> String {
+        let yy = dt.year % 100;
+        let (mm, dd) = (dt.month, dt.day);
+
+        match self {
+            Self::YearMonthDay => format!("'{yy:02} {mm:02} {dd:02}"),
+            Self::MonthDayYear => format!("{mm:02} {dd:02} '{yy:02}"),
+            Self::DayMonthYear => format!("{dd:02} {mm:02} '{yy:02}"),
+            Self::FullYearMonthDay => format!("{:04} {mm:02} {dd:02}", dt.year),
+            Self::YearMonthDayTime => {
+                format!("'{yy:02} {mm:02} {dd:02}  {:02}:{:02}", dt.hour, dt.minute)
+            }
+        }
+    }
+
+    fn label(&self) -> &str {
+        match self {
+            Self::YearMonthDay => "'YY MM DD",
+            Self::MonthDayYear => "MM DD 'YY",
+            Self::DayMonthYear => "DD MM 'YY",
+            Self::FullYearMonthDay => "YYYY MM DD",
+            Self::YearMonthDayTime => "'YY MM DD  hh:mm",
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct DateStamp {
+    format: DateFormat,
+    font_color: egui::Color32,
+    font: FontChoice,
+    font_size: f32,
+    /// Glow strength in percent
+    glow: f32,
+    /// Soften edge of digits like ink bleed on film
+    bleed: bool,
+    effects: TextEffects,
+}
+
+impl core::default::Default for DateStamp {
+    fn default() -> Self {
+        let [r, g, b, a] = FILM_COLOR.data();
+
+        Self {
+            format: DateFormat::YearMonthDay,
+            font_color: egui::Color32::from_rgba_unmultiplied_const(r, g, b, a),
+            font: FontChoice::Bundled(OverlayFont::Digits),
+            font_size: DEFAULT_FONT_SIZE as f32,
+            glow: 40.0,
+            bleed: true,
+            effects: TextEffects::default(),
+        }
+    }
+}
+
+impl DateStamp {
+    fn rel_size<F: Copy + num_traits::AsPrimitive<f32>, G: Copy + num_traits::AsPrimitive<f32>>(
+        &self,
+        size: F,
+        dyn_wh: G,
+    ) -> f32 {
+        size.as_() * (self.font_size / (DEFAULT_FONT_SIZE as f32)) * (dyn_wh.as_() / 4000.0)
+    }
+}
+
+impl Theme for DateStamp {
+    fn unique_name(&self) -> &'static str {
+        "date_stamp"
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        t!("theme.date_stamp")
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let mut canvas = pi.developed(export_config)?.to_rgba8();
+
+        let Some(datetime) = pi.view_exif.get_datetime() else {
+            log::warn!(
+                "Cannot parse datetime {:?}, save without date stamp",
+                pi.view_exif.datetime
+            );
+            return export_config
+                .output_format
+                .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path);
+        };
+
+        let (dyn_w, dyn_h) = canvas.dimensions();
+        let dyn_wh = dyn_w.max(dyn_h);
+        let font = self.font.chain();
+        let color = crate::theme::color32_to_rgba(self.font_color);
+        let scale = ab_glyph::PxScale::from(self.rel_size(110, dyn_wh));
+        let margin = self.rel_size(150, dyn_wh);
+
+        let text = self.format.format(&datetime);
+        let (text_w, text_h) = text_dimensions(scale, &font, &text);
+
+        // Draw digits on separated mask with padding for glow
+        let sigma = self.rel_size(12, dyn_wh);
+        let padding = ((sigma * 3.0).ceil() as u32).max(self.effects.padding(scale.y));
+        let mask = crate::theme::text_mask(scale, &font, &text, padding);
+
+        let (text_x, text_y) = (
+            dyn_w as f32 - margin - text_w,
+            dyn_h as f32 - margin - text_h,
+        );
+        self.effects
+            .draw_plate(&mut canvas, text_x, text_y, text_w, text_h, scale.y);
+
+        let x = text_x.round() as i64 - padding as i64;
+        let y = text_y.round() as i64 - padding as i64;
+        self.effects
+            .draw_backdrop(&mut canvas, &mask, x, y, scale.y);
+
+        if self.glow > 0.0 {
+            let glow = image::imageops::fast_blur(&mask, sigma);
+            let alpha = (self.glow.clamp(0.0, 100.0) * 2.55).round() as u8;
+            crate::theme::blend_mask_mut(
+                &mut canvas,
+                &glow,
+                x,
+                y,
+                image::Rgba([color[0], color[1], color[2], alpha]),
+            );
+        }
+
+        let mask = if self.bleed {
+            image::imageops::fast_blur(&mask, (sigma * 0.08).max(0.6))
+        } else {
+            mask
+        };
+        crate::theme::blend_mask_mut(&mut canvas, &mask, x, y, color);
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.date_stamp_config.format"));
+                egui::ComboBox::from_id_salt("date_stamp_format")
+                    .selected_text(self.format.label())
+                    .show_ui(ui, |ui| {
+                        for format in DateFormat::iter() {
+                            ui.selectable_value(&mut self.format, format, format.label());
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.font"));
+                self.font.update_ui(ui, "date_stamp_font");
+            });
+            ui.add(egui::Slider::new(&mut self.font_size, 1.0..=100.0).text(t!("theme.font_size")))
+                .on_hover_text(t!(
+                    "theme.font_size_description",
+                    default = DEFAULT_FONT_SIZE
+                ));
+            ui.add(
+                egui::Slider::new(&mut self.glow, 0.0..=100.0)
+                    .suffix("%")
+                    .text(t!("theme.date_stamp_config.glow")),
+            );
+            ui.checkbox(&mut self.bleed, t!("theme.date_stamp_config.bleed"));
+            self.effects.update_ui(ui, "date_stamp_text_effects");
+            ui.add_space(1.0);
+            egui::color_picker::color_picker_color32(
+                ui,
+                &mut self.font_color,
+                egui::color_picker::Alpha::Opaque,
+            );
+        });
+    }
+}
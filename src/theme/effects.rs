@@ -0,0 +1,166 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Shared image effects for themes.
+//! Masks are `GrayImage` that luma means coverage (0 = transparent, 255 = opaque).
+
+use image::{GrayImage, Luma, RgbaImage};
+
+/// Coverage mask of rounded rectangle with anti-aliased corners
+pub(crate) fn rounded_rect_mask(width: u32, height: u32, radius: f32) -> GrayImage {
+    let radius = radius.clamp(0.0, width.min(height) as f32 / 2.0);
+    if radius < 0.5 {
+        return GrayImage::from_pixel(width, height, Luma([255]));
+    }
+
+    let (w, h) = (width as f32, height as f32);
+    GrayImage::from_fn(width, height, |x, y| {
+        let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+        let dx = px - px.clamp(radius, w - radius);
+        let dy = py - py.clamp(radius, h - radius);
+        let coverage = (radius - (dx * dx + dy * dy).sqrt() + 0.5).clamp(0.0, 1.0);
+        Luma([(coverage * 255.0).round() as u8])
+    })
+}
+
+/// Multiply alpha channel of image with mask
+pub(crate) fn apply_mask_alpha(image: &mut RgbaImage, mask: &GrayImage) {
+    for (px, m) in image.pixels_mut().zip(mask.pixels()) {
+        px[3] = (px[3] as u32 * m[0] as u32 / 255) as u8;
+    }
+}
+
+/// Rotate clockwise in degree, canvas expands to keep every corner
+pub(crate) fn rotate_expand(image: &RgbaImage, degree: f32) -> RgbaImage {
+    if degree.abs() < 0.01 {
+        return image.clone();
+    }
+
+    let theta = degree.to_radians();
+    let (w, h) = (image.width() as f32, image.height() as f32);
+    let (cos, sin) = (theta.cos().abs(), theta.sin().abs());
+    let (expand_w, expand_h) = (
+        (w * cos + h * sin).ceil() as u32,
+        (w * sin + h * cos).ceil() as u32,
+    );
+
+    let (expand_w, expand_h) = (expand_w.max(image.width()), expand_h.max(image.height()));
+    let mut expanded = RgbaImage::new(expand_w, expand_h);
+    image::imageops::replace(
+        &mut expanded,
+        image,
+        ((expand_w - image.width()) / 2) as i64,
+        ((expand_h - image.height()) / 2) as i64,
+    );
+
+    imageproc::geometric_transformations::rotate_about_center(
+        &expanded,
+        theta,
+        imageproc::geometric_transformations::Interpolation::Bilinear,
+        image::Rgba([0, 0, 0, 0]),
+    )
+}
+
+/// Blurred copy of mask with padding to keep blurred edge.
+/// Return mask and padding, draw it on `(x - padding, y - padding)`
+pub(crate) fn soft_shadow(mask: &GrayImage, sigma: f32) -> (GrayImage, u32) {
+    let padding = (sigma * 3.0).ceil() as u32;
+    let mut padded = GrayImage::new(mask.width() + padding * 2, mask.height() + padding * 2);
+    image::imageops::replace(&mut padded, mask, padding as i64, padding as i64);
+
+    if sigma > 0.0 {
+        (image::imageops::fast_blur(&padded, sigma), padding)
+    } else {
+        (padded, padding)
+    }
+}
+
+/// Enlarge image to cover `width` x `height`, then blur and darken it.
+/// `sigma` is based on output size, `brightness` is 0.0 ~ 1.0
+pub(crate) fn blurred_cover(
+    image: &RgbaImage,
+    width: u32,
+    height: u32,
+    sigma: f32,
+    brightness: f32,
+) -> RgbaImage {
+    use image::imageops::FilterType;
+
+    // Heavy blur doesn't need full resolution
+    const DOWN: u32 = 4;
+    let (small_w, small_h) = ((width / DOWN).max(1), (height / DOWN).max(1));
+
+    let cover = (small_w as f32 / image.width() as f32).max(small_h as f32 / image.height() as f32);
+    let (cover_w, cover_h) = (
+        ((image.width() as f32 * cover).ceil() as u32).max(small_w),
+        ((image.height() as f32 * cover).ceil() as u32).max(small_h),
+    );
+    let resized = image::imageops::resize(image, cover_w, cover_h, FilterType::Triangle);
+    let cropped = image::imageops::crop_imm(
+        &resized,
+        (cover_w - small_w) / 2,
+        (cover_h - small_h) / 2,
+        small_w,
+        small_h,
+    )
+    .to_image();
+
+    let blurred = image::imageops::fast_blur(&cropped, sigma / DOWN as f32);
+    let mut output = image::imageops::resize(&blurred, width, height, FilterType::Triangle);
+
+    let brightness = brightness.clamp(0.0, 1.0);
+    for px in output.pixels_mut() {
+        for c in 0..3 {
+            px[c] = (px[c] as f32 * brightness).round() as u8;
+        }
+        px[3] = 255;
+    }
+
+    output
+}
+
+/// Largest area of `ratio` (width / height) inside of image.
+/// `offset` moves the area in percent of remaining space, -100 (left, top) ~ 100 (right, bottom)
+pub(crate) fn crop_to_aspect(image: &RgbaImage, ratio: f32, offset: [f32; 2]) -> RgbaImage {
+    let (w, h) = image.dimensions();
+    let (crop_w, crop_h) = if w as f32 / h as f32 > ratio {
+        (((h as f32 * ratio).round() as u32).clamp(1, w), h)
+    } else {
+        (w, ((w as f32 / ratio).round() as u32).clamp(1, h))
+    };
+
+    let [fx, fy] = offset.map(|x| (x.clamp(-100.0, 100.0) + 100.0) / 200.0);
+    let x = ((w - crop_w) as f32 * fx).round() as u32;
+    let y = ((h - crop_h) as f32 * fy).round() as u32;
+    image::imageops::crop_imm(image, x, y, crop_w, crop_h).to_image()
+}
+
+/// Linear gradient from `from` to `to`, `angle` 0 is top to bottom and 90 is left to right
+pub(crate) fn linear_gradient(
+    width: u32,
+    height: u32,
+    from: image::Rgba<u8>,
+    to: image::Rgba<u8>,
+    angle: f32,
+) -> RgbaImage {
+    // Direction of gradient, 0 degree goes downward
+    let theta = angle.to_radians();
+    let (dx, dy) = (theta.sin(), theta.cos());
+    let (w, h) = (width as f32, height as f32);
+
+    // Project corners to find range of gradient
+    let corners = [(0.0, 0.0), (w, 0.0), (0.0, h), (w, h)].map(|(x, y)| x * dx + y * dy);
+    let min = corners.iter().copied().fold(f32::MAX, f32::min);
+    let max = corners.iter().copied().fold(f32::MIN, f32::max);
+    let range = (max - min).max(1.0);
+
+    RgbaImage::from_fn(width, height, |x, y| {
+        let t = ((x as f32 + 0.5) * dx + (y as f32 + 0.5) * dy - min) / range;
+        image::Rgba(std::array::from_fn(|c| {
+            (from[c] as f32 + (to[c] as f32 - from[c] as f32) * t).round() as u8
+        }))
+    })
+}
@@ -4,41 +4,380 @@
  * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
  */
 
-use crate::theme::Theme;
-use ab_glyph::{Font, PxScale, ScaleFont};
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::theme::{
+    Theme, anchor::Anchor, sampler::ContentSampler, template, text_dimensions,
+    text_effects::TextEffects,
+};
 use imageproc::integral_image::ArrayData;
 use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+/// Label drawn in front of value on right block
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PairPrefix {
+    FNumber,
+    Shutter,
+    Iso,
+    Focal,
+    Text(String),
+}
+
+impl PairPrefix {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::FNumber => t!("theme.film_config.prefix_fnumber"),
+            Self::Shutter => t!("theme.film_config.prefix_shutter"),
+            Self::Iso => t!("theme.film_config.prefix_iso"),
+            Self::Focal => t!("theme.film_config.prefix_focal"),
+            Self::Text(x) => std::borrow::Cow::Owned(x.clone()),
+        }
+    }
+
+    fn update_ui(&mut self, ui: &mut egui::Ui, id_salt: impl std::hash::Hash) {
+        let custom = t!("theme.film_config.prefix_custom");
+
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(match self {
+                Self::Text(_) => custom.clone(),
+                _ => self.label(),
+            })
+            .show_ui(ui, |ui| {
+                for prefix in [Self::FNumber, Self::Shutter, Self::Iso, Self::Focal] {
+                    let label = prefix.label();
+                    ui.selectable_value(self, prefix, label);
+                }
+                if ui
+                    .selectable_label(matches!(self, Self::Text(_)), custom)
+                    .clicked()
+                    && !matches!(self, Self::Text(_))
+                {
+                    *self = Self::Text(self.label().into_owned());
+                }
+            });
+
+        if let Self::Text(text) = self {
+            ui.add(egui::TextEdit::singleline(text).desired_width(48.0));
+        }
+    }
+}
+
+/// Line of left block
+#[derive(Clone, Deserialize, Serialize)]
+pub struct FilmLine {
+    enabled: bool,
+    template: String,
+}
+
+/// Prefix and value pair of right block
+#[derive(Clone, Deserialize, Serialize)]
+pub struct FilmPair {
+    enabled: bool,
+    prefix: PairPrefix,
+    template: String,
+}
+
+/// Alignment of rows inside of block
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum BlockAlign {
+    /// Same side as anchor, right aligned on right corners
+    Anchor,
+    Left,
+    Center,
+    Right,
+}
+
+impl BlockAlign {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::Anchor => t!("theme.film_config.align_anchor"),
+            Self::Left => t!("theme.film_config.align_left"),
+            Self::Center => t!("theme.film_config.align_center"),
+            Self::Right => t!("theme.film_config.align_right"),
+        }
+    }
+
+    /// 0.0 left / 0.5 center / 1.0 right
+    fn factor(&self, anchor: Anchor) -> f32 {
+        match self {
+            Self::Anchor => anchor.fx(),
+            Self::Left => 0.0,
+            Self::Center => 0.5,
+            Self::Right => 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct FilmLayout {
+    /// Position of camera and lens lines
+    pub lines_anchor: Anchor,
+    /// Position of prefix and value pairs
+    pub pairs_anchor: Anchor,
+    pub align: BlockAlign,
+    /// Distance between rows in percent of row height
+    pub line_spacing: f32,
+    /// Margin from photo edge in percent of long edge
+    pub margin: f32,
+}
+
+impl core::default::Default for FilmLayout {
+    fn default() -> Self {
+        Self {
+            lines_anchor: Anchor::BottomLeft,
+            pairs_anchor: Anchor::BottomRight,
+            align: BlockAlign::Anchor,
+            line_spacing: 100.0,
+            margin: 3.0,
+        }
+    }
+}
+
+impl FilmLayout {
+    /// Anchors are disabled on automatic placement
+    fn update_ui(&mut self, ui: &mut egui::Ui, id_salt: &str, auto_placement: bool) {
+        ui.add_enabled_ui(!auto_placement, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.film_config.lines_anchor"));
+                self.lines_anchor.update_ui(ui, (id_salt, "lines_anchor"));
+                ui.label(t!("theme.film_config.pairs_anchor"));
+                self.pairs_anchor.update_ui(ui, (id_salt, "pairs_anchor"));
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label(t!("theme.film_config.align"));
+            egui::ComboBox::from_id_salt((id_salt, "align"))
+                .selected_text(self.align.label())
+                .show_ui(ui, |ui| {
+                    for align in BlockAlign::iter() {
+                        ui.selectable_value(&mut self.align, align, align.label());
+                    }
+                });
+        });
+        ui.add(
+            egui::Slider::new(&mut self.line_spacing, 50.0..=200.0)
+                .suffix("%")
+                .text(t!("theme.film_config.line_spacing")),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.margin, 0.0..=15.0)
+                .suffix("%")
+                .text(t!("theme.film_config.margin")),
+        );
+    }
+}
 
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct Film {
+    horizontal: FilmLayout,
+    vertical: FilmLayout,
     font_color: egui::Color32,
+    /// Keep `font_color` only when it is readable on photo, otherwise light or dark one
+    auto_color: bool,
+    /// Move blocks to least busy corners of photo, anchors of layout are ignored
+    auto_placement: bool,
+    font: FontChoice,
     font_size: f32,
+    /// Drawn from top to bottom on `FilmLayout::lines_anchor`
+    lines: Vec<FilmLine>,
+    /// Drawn from top to bottom on `FilmLayout::pairs_anchor`
+    pairs: Vec<FilmPair>,
+    effects: TextEffects,
 }
 
-const FILM_COLOR: image::Rgba<u8> = image::Rgba([255, 153, 0, 255]);
+pub(crate) const FILM_COLOR: image::Rgba<u8> = image::Rgba([255, 153, 0, 255]);
 const DEFAULT_FONT_SIZE: u32 = 25;
 
-fn text_dimensions(scale: PxScale, font: &impl Font, text: &str) -> (f32, f32) {
-    let scaled = font.as_scaled(scale);
-    (
-        text.chars()
-            .map(|c| scaled.h_advance(font.glyph_id(c)))
-            .sum::<f32>(),
-        scaled.height(),
-    )
-}
-
 impl core::default::Default for Film {
     fn default() -> Self {
         let [r, g, b, a] = FILM_COLOR.data();
 
         Self {
+            horizontal: FilmLayout::default(),
+            vertical: FilmLayout::default(),
             font_color: egui::Color32::from_rgba_unmultiplied_const(r, g, b, a),
+            auto_color: false,
+            auto_placement: false,
+            font: FontChoice::Bundled(OverlayFont::Digits),
             font_size: DEFAULT_FONT_SIZE as f32,
+            lines: vec![
+                FilmLine {
+                    enabled: true,
+                    template: "{camera_mnf}  {camera_model}".to_owned(),
+                },
+                FilmLine {
+                    enabled: true,
+                    template: "{lens_model}".to_owned(),
+                },
+            ],
+            pairs: vec![
+                FilmPair {
+                    enabled: true,
+                    prefix: PairPrefix::FNumber,
+                    template: "{fnumber}".to_owned(),
+                },
+                FilmPair {
+                    enabled: true,
+                    prefix: PairPrefix::Shutter,
+                    template: "{exposure}".to_owned(),
+                },
+                FilmPair {
+                    enabled: true,
+                    prefix: PairPrefix::Iso,
+                    template: "{iso}".to_owned(),
+                },
+            ],
+            effects: TextEffects::default(),
+        }
+    }
+}
+
+enum ListAction {
+    Up(usize),
+    Down(usize),
+    Remove(usize),
+}
+
+impl ListAction {
+    /// Up, down and remove buttons of item in list
+    fn buttons(ui: &mut egui::Ui, idx: usize, len: usize) -> Option<Self> {
+        let mut action = None;
+        if ui.add_enabled(idx > 0, egui::Button::new("⏶")).clicked() {
+            action = Some(Self::Up(idx));
+        }
+        if ui
+            .add_enabled(idx + 1 < len, egui::Button::new("⏷"))
+            .clicked()
+        {
+            action = Some(Self::Down(idx));
+        }
+        if ui.button("🗑").clicked() {
+            action = Some(Self::Remove(idx));
+        }
+        action
+    }
+
+    fn apply<T>(self, list: &mut Vec<T>) {
+        match self {
+            Self::Up(idx) => list.swap(idx - 1, idx),
+            Self::Down(idx) => list.swap(idx, idx + 1),
+            Self::Remove(idx) => {
+                list.remove(idx);
+            }
+        }
+    }
+}
+
+/// Row of text block, segments are (x in row, scale, text) and share baseline
+struct Row {
+    segments: Vec<(f32, ab_glyph::PxScale, String)>,
+    width: f32,
+    height: f32,
+    ascent: f32,
+}
+
+impl Row {
+    /// Segments are (scale, text, spacing after it)
+    fn new(
+        font: &crate::fonts::FontChain,
+        segments: Vec<(ab_glyph::PxScale, String, f32)>,
+    ) -> Self {
+        let mut row = Self {
+            segments: Vec::new(),
+            width: 0.0,
+            height: 0.0,
+            ascent: 0.0,
+        };
+
+        let last = segments.len().saturating_sub(1);
+        for (idx, (scale, text, spacing)) in segments.into_iter().enumerate() {
+            let (w, h) = text_dimensions(scale, font, &text);
+            row.segments.push((row.width, scale, text));
+            row.width += w + if idx < last { spacing } else { 0.0 };
+            row.height = row.height.max(h);
+            row.ascent = row.ascent.max(font.ascent(scale));
+        }
+        row
+    }
+}
+
+/// Rows drawn from top to bottom
+struct Block {
+    rows: Vec<Row>,
+    width: f32,
+    /// Top of first row to baseline of last row, bottom anchor puts baseline on margin
+    height: f32,
+    /// Below baseline of last row, covered by plate
+    descent: f32,
+    /// Font size in pixel for text effects
+    size: f32,
+    /// Row advance over row height
+    spacing: f32,
+}
+
+impl Block {
+    fn new(rows: Vec<Row>, size: f32, spacing: f32) -> Self {
+        // Spacing is not added below last row
+        let descent = rows.last().map_or(0.0, |x| x.height - x.ascent);
+        let height = rows.iter().map(|x| x.height * spacing).sum::<f32>()
+            - rows.last().map_or(0.0, |x| x.height * (spacing - 1.0))
+            - descent;
+
+        Self {
+            width: rows.iter().map(|x| x.width).fold(0.0, f32::max),
+            height,
+            descent,
+            rows,
+            size,
+            spacing,
+        }
+    }
+
+    /// Draw on (x, y), each row is aligned along with `align` (0.0 left / 1.0 right)
+    #[allow(clippy::too_many_arguments)]
+    fn draw(
+        &self,
+        canvas: &mut image::RgbaImage,
+        font: &crate::fonts::FontChain,
+        effects: &TextEffects,
+        x: f32,
+        y: f32,
+        align: f32,
+        color: image::Rgba<u8>,
+    ) {
+        if self.rows.is_empty() {
+            return;
+        }
+        effects.draw_plate(
+            canvas,
+            x,
+            y,
+            self.width,
+            self.height + self.descent,
+            self.size,
+        );
+
+        let mut top = y;
+        for row in &self.rows {
+            let row_x = x + (self.width - row.width) * align;
+            for (seg_x, scale, text) in &row.segments {
+                let seg_y = top + row.ascent - font.ascent(*scale);
+                effects.draw_text(canvas, font, *scale, row_x + seg_x, seg_y, color, text);
+            }
+            top += row.height * self.spacing;
         }
     }
 }
 
+/// Corners for automatic placement
+const CORNERS: [Anchor; 4] = [
+    Anchor::TopLeft,
+    Anchor::TopRight,
+    Anchor::BottomLeft,
+    Anchor::BottomRight,
+];
+
 impl Film {
     fn rel_size<F: Copy + num_traits::AsPrimitive<f32>, G: Copy + num_traits::AsPrimitive<f32>>(
         &self,
@@ -74,83 +413,193 @@ impl Theme for Film {
     ) -> Result<(), image::ImageError> {
         let exif = &pi.view_exif;
         let color: image::Rgba<u8> = crate::theme::color32_to_rgba(self.font_color);
-        let scale_config = &export_config.scale_config;
-        let mut dyn_image = pi.with_scale_and_orientation(*scale_config)?;
-        let (dyn_w, dyn_h) = (dyn_image.width(), dyn_image.height());
+        let mut canvas = pi.developed(export_config)?.to_rgba8();
+        let (dyn_w, dyn_h) = canvas.dimensions();
         let dyn_wh = dyn_w.max(dyn_h);
-        let font = crate::fonts::FONT_DIGITS.clone();
-
-        #[rustfmt::skip]
-        macro_rules! draw {
-            ($xxx:expr, $yyy:expr, $scale:expr, $text:expr) => {
-                imageproc::drawing::draw_text_mut(&mut dyn_image, color, ($xxx) as i32, ($yyy as f32 - font.as_scaled($scale).ascent()) as i32, $scale, &font, $text);
-            };
-        }
-
-        let margin = self.rel_size(120, dyn_wh).trunc() as i32;
+        let font = self.font.chain();
 
-        // Left
-        let base_y = dyn_h as i32 - margin;
+        // Orientation from `SimplifiedExif::is_vertical_rotated` is already applied
+        let layout = if dyn_h > dyn_w {
+            &self.vertical
+        } else {
+            &self.horizontal
+        };
+        let margin = (dyn_wh as f32 * layout.margin / 100.0).trunc();
+        let spacing_ratio = layout.line_spacing / 100.0;
 
+        // Left block, camera and lens
         let cam_scale = self.rel_scale(75, dyn_wh);
-        draw!(
-            margin,
-            base_y as f32 - cam_scale.y,
-            cam_scale,
-            &format!("{}  {}", exif.camera_mnf, exif.camera_model)
-        );
-
-        draw!(margin, base_y, cam_scale, &exif.lens_model.clone());
-
-        // Right
-        let pairs = {
-            let mut list = Vec::new();
-            if let Some(f) = exif.get_fnumber() {
-                list.push(("F", f));
-            }
-            if let Some(sec) = exif.get_exposure() {
-                list.push(("SEC", sec));
-            }
-            if let Some(iso) = exif.get_iso() {
-                list.push(("ISO", iso));
-            }
-            list
-        };
+        let lines = self
+            .lines
+            .iter()
+            .filter(|x| x.enabled)
+            .map(|x| template::render(&x.template, exif).trim().to_owned())
+            .filter(|x| !x.is_empty())
+            .map(|x| Row::new(&font, vec![(cam_scale, x, 0.0)]))
+            .collect::<Vec<_>>();
 
+        // Right block, pair without value is skipped
         let prefix_scale = self.rel_scale(65, dyn_wh);
         let number_scale = self.rel_scale(100, dyn_wh);
         let spacing = self.rel_size(8.0, dyn_wh);
-        let mut y: f32 = base_y as f32;
+        let pairs = self
+            .pairs
+            .iter()
+            .filter(|x| x.enabled)
+            .filter_map(|x| {
+                let number = template::render(&x.template, exif).trim().to_owned();
+                (!number.is_empty()).then(|| {
+                    Row::new(
+                        &font,
+                        vec![
+                            (prefix_scale, x.prefix.label().into_owned(), spacing),
+                            (number_scale, number, 0.0),
+                        ],
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
 
-        for (prefix, number) in pairs.iter().rev() {
-            let (prefix_w, prefix_h) = text_dimensions(prefix_scale, &font, prefix);
-            let (number_w, number_h) = text_dimensions(number_scale, &font, number);
-            let line_h = number_h.max(prefix_h);
-            let total_w = prefix_w + spacing + number_w;
+        let blocks = [
+            Block::new(lines, cam_scale.y, spacing_ratio),
+            Block::new(pairs, number_scale.y, spacing_ratio),
+        ];
 
-            // For right alignment
-            let x_right = dyn_w as f32 - margin as f32;
-            let x_prefix = (x_right - total_w).round() as i32;
-            let x_number = (x_right - number_w).round() as i32;
+        let sampler =
+            (self.auto_color || self.auto_placement).then(|| ContentSampler::new(&canvas));
+        let rect = |block: &Block, anchor: Anchor| {
+            let (x, y) = anchor.position(
+                dyn_w as f32,
+                dyn_h as f32,
+                block.width,
+                block.height,
+                margin,
+            );
+            (x, y, block.width, block.height + block.descent)
+        };
 
-            draw!(x_prefix, y, prefix_scale, prefix);
-            draw!(x_number, y, number_scale, number);
+        let mut anchors = [layout.lines_anchor, layout.pairs_anchor];
+        if let (true, Some(sampler)) = (self.auto_placement, &sampler) {
+            let cost = |block: &Block, anchor: Anchor| {
+                if block.rows.is_empty() {
+                    return 0.0;
+                }
+                let (x, y, w, h) = rect(block, anchor);
+                sampler.busyness(x, y, w, h)
+            };
+
+            // Pick different corners for each block with least busyness on sum
+            let mut best = f32::MAX;
+            for first in CORNERS {
+                for second in CORNERS.into_iter().filter(|x| *x != first) {
+                    let total = cost(&blocks[0], first) + cost(&blocks[1], second);
+                    if total < best {
+                        best = total;
+                        anchors = [first, second];
+                    }
+                }
+            }
+        }
 
-            y -= line_h;
+        for (block, anchor) in blocks.iter().zip(anchors) {
+            let (x, y, w, h) = rect(block, anchor);
+            let color = match (&sampler, self.auto_color) {
+                (Some(sampler), true) => {
+                    crate::theme::sampler::contrast_color(color, sampler.luma_stats(x, y, w, h).0)
+                }
+                _ => color,
+            };
+            let align = layout.align.factor(anchor);
+            block.draw(&mut canvas, &font, &self.effects, x, y, align, color);
         }
 
         export_config
             .output_format
-            .save_image(&dyn_image, output_path)
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
     }
 
     fn ui_config(&mut self, ui: &mut egui::Ui) {
         ui.vertical(|ui| {
+            ui.checkbox(
+                &mut self.auto_placement,
+                t!("theme.film_config.auto_placement"),
+            );
+            ui.label(t!("theme.horizontal"));
+            self.horizontal
+                .update_ui(ui, "film_horizontal", self.auto_placement);
+            ui.add_space(1.0);
+
+            ui.label(t!("theme.vertical"));
+            self.vertical
+                .update_ui(ui, "film_vertical", self.auto_placement);
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.font"));
+                self.font.update_ui(ui, "film_font");
+            });
             ui.add(egui::Slider::new(&mut self.font_size, 1.0..=100.0).text(t!("theme.font_size")))
                 .on_hover_text(t!(
                     "theme.font_size_description",
                     default = DEFAULT_FONT_SIZE
                 ));
+
+            ui.separator();
+            ui.label(t!("theme.film_config.lines"));
+            let len = self.lines.len();
+            let mut action = None;
+            for (idx, line) in self.lines.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut line.enabled, "");
+                    ui.add_enabled_ui(line.enabled, |ui| {
+                        template::update_ui(ui, &mut line.template);
+                    });
+                    if let Some(x) = ListAction::buttons(ui, idx, len) {
+                        action = Some(x);
+                    }
+                });
+            }
+            if let Some(action) = action {
+                action.apply(&mut self.lines);
+            }
+            if ui.button(t!("theme.film_config.add_line")).clicked() {
+                self.lines.push(FilmLine {
+                    enabled: true,
+                    template: String::new(),
+                });
+            }
+
+            ui.separator();
+            ui.label(t!("theme.film_config.pairs"));
+            let len = self.pairs.len();
+            let mut action = None;
+            for (idx, pair) in self.pairs.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut pair.enabled, "");
+                    ui.add_enabled_ui(pair.enabled, |ui| {
+                        pair.prefix.update_ui(ui, ("film_pair_prefix", idx));
+                        template::update_ui(ui, &mut pair.template);
+                    });
+                    if let Some(x) = ListAction::buttons(ui, idx, len) {
+                        action = Some(x);
+                    }
+                });
+            }
+            if let Some(action) = action {
+                action.apply(&mut self.pairs);
+            }
+            if ui.button(t!("theme.film_config.add_pair")).clicked() {
+                self.pairs.push(FilmPair {
+                    enabled: true,
+                    prefix: PairPrefix::Text(String::new()),
+                    template: String::new(),
+                });
+            }
+
+            ui.separator();
+            self.effects.update_ui(ui, "film_text_effects");
+            ui.checkbox(&mut self.auto_color, t!("theme.auto_color"))
+                .on_hover_text(t!("theme.auto_color_description"));
             ui.add_space(1.0);
             egui::color_picker::color_picker_color32(
                 ui,
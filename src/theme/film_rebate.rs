@@ -0,0 +1,227 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! 35mm negative strip around photo, black rebate with sprocket holes,
+//! film stock name, edge code and frame number printed on the edge.
+//! Sizes follow 135 film in millimeter where short edge of photo is 24mm.
+//! Vertical photo is drawn on film running vertically.
+
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::theme::{
+    Theme, effects, film::FILM_COLOR, template, text_dimensions, text_effects::TextEffects,
+};
+use imageproc::integral_image::ArrayData;
+use rust_i18n::t;
+
+const DEFAULT_FONT_SIZE: u32 = 25;
+
+/// Short edge of 135 film frame
+const FRAME_MM: f32 = 24.0;
+/// Film outside of frame on top and bottom, (35 - 24) / 2
+const REBATE_MM: f32 = 5.5;
+/// Half of gap between frames
+const SIDE_MM: f32 = 1.0;
+/// Film edge to sprocket hole
+const HOLE_OFFSET_MM: f32 = 2.0;
+const HOLE_W_MM: f32 = 2.8;
+const HOLE_H_MM: f32 = 1.98;
+const HOLE_RADIUS_MM: f32 = 0.5;
+/// Distance between sprocket holes, 8 holes per frame
+const HOLE_PITCH_MM: f32 = 4.75;
+/// Height of edge text on default font size
+const TEXT_MM: f32 = 1.2;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct FilmRebate {
+    /// Printed along top edge
+    film_stock: String,
+    /// Printed along bottom edge next to frame numbers, see `template`
+    edge_code: String,
+    /// Frame number of first image in list
+    start_number: usize,
+    rebate_color: egui::Color32,
+    hole_color: egui::Color32,
+    font_color: egui::Color32,
+    font: FontChoice,
+    font_size: f32,
+    /// Outline, shadow and plate of edge print
+    effects: TextEffects,
+}
+
+impl core::default::Default for FilmRebate {
+    fn default() -> Self {
+        let [r, g, b, a] = FILM_COLOR.data();
+
+        Self {
+            film_stock: "CHAMA 400".to_owned(),
+            edge_code: "{date}".to_owned(),
+            start_number: 1,
+            rebate_color: egui::Color32::from_gray(0x12),
+            hole_color: egui::Color32::from_gray(0xF0),
+            font_color: egui::Color32::from_rgba_unmultiplied_const(r, g, b, a),
+            font: FontChoice::Bundled(OverlayFont::Digits),
+            font_size: DEFAULT_FONT_SIZE as f32,
+            effects: TextEffects::default(),
+        }
+    }
+}
+
+impl FilmRebate {
+    /// Film strip on horizontal photo
+    fn compose(
+        &self,
+        photo: &image::RgbaImage,
+        frame_number: usize,
+        edge_code: &str,
+    ) -> image::RgbaImage {
+        let (img_w, img_h) = photo.dimensions();
+        let mm = img_h as f32 / FRAME_MM;
+        let px = |x: f32| (x * mm).round() as u32;
+
+        let (rebate, side) = (px(REBATE_MM), px(SIDE_MM));
+        let (canvas_w, canvas_h) = (img_w + side * 2, img_h + rebate * 2);
+        let mut canvas = image::RgbaImage::from_pixel(
+            canvas_w,
+            canvas_h,
+            crate::theme::color32_to_rgba(self.rebate_color),
+        );
+        image::imageops::overlay(&mut canvas, photo, side as i64, rebate as i64);
+
+        // Sprocket holes, centered on strip
+        let (hole_w, hole_h) = (px(HOLE_W_MM).max(1), px(HOLE_H_MM).max(1));
+        let hole = effects::rounded_rect_mask(hole_w, hole_h, HOLE_RADIUS_MM * mm);
+        let hole_color = crate::theme::color32_to_rgba(self.hole_color);
+        let pitch = HOLE_PITCH_MM * mm;
+        let count = (canvas_w as f32 / pitch).floor() as u32 + 1;
+        let start = (canvas_w as f32 - (count - 1) as f32 * pitch - hole_w as f32) / 2.0;
+        let hole_y = [px(HOLE_OFFSET_MM), canvas_h - px(HOLE_OFFSET_MM) - hole_h];
+
+        for idx in 0..count {
+            let x = (start + pitch * idx as f32).round() as i64;
+            for y in hole_y {
+                crate::theme::blend_mask_mut(&mut canvas, &hole, x, y as i64, hole_color);
+            }
+        }
+
+        // Edge print between film edge and sprocket holes
+        let font = self.font.chain();
+        let color = crate::theme::color32_to_rgba(self.font_color);
+        let scale =
+            ab_glyph::PxScale::from(TEXT_MM * mm * self.font_size / DEFAULT_FONT_SIZE as f32);
+        let strip_h = HOLE_OFFSET_MM * mm;
+        let (_, text_h) = text_dimensions(scale, &font, "");
+        let top_y = (strip_h - text_h) / 2.0;
+        let bottom_y = canvas_h as f32 - strip_h + top_y;
+
+        let mut print = |x: f32, y: f32, text: &str| {
+            if !text.is_empty() {
+                let (text_w, _) = text_dimensions(scale, &font, text);
+                self.effects
+                    .draw_plate(&mut canvas, x, y, text_w, text_h, scale.y);
+                self.effects
+                    .draw_text(&mut canvas, &font, scale, x, y, color, text);
+            }
+        };
+
+        let film_stock = self.film_stock.trim();
+        for x in [0.08, 0.58] {
+            print(canvas_w as f32 * x, top_y, film_stock);
+        }
+
+        print(canvas_w as f32 * 0.08, bottom_y, &frame_number.to_string());
+        print(
+            canvas_w as f32 * 0.5,
+            bottom_y,
+            &format!("▶{frame_number}A"),
+        );
+        print(canvas_w as f32 * 0.72, bottom_y, edge_code);
+
+        canvas
+    }
+}
+
+impl Theme for FilmRebate {
+    fn unique_name(&self) -> &'static str {
+        "film_rebate"
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        t!("theme.film_rebate")
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let photo = pi.developed(export_config)?.to_rgba8();
+        let frame_number = self.start_number + pi.sequence.saturating_sub(1);
+        let edge_code = template::render(&self.edge_code, &pi.view_exif)
+            .trim()
+            .to_owned();
+
+        // Film runs vertically for vertical photo, so does the edge print
+        let canvas = if photo.height() > photo.width() {
+            let rotated = image::imageops::rotate90(&photo);
+            let strip = self.compose(&rotated, frame_number, &edge_code);
+            image::imageops::rotate270(&strip)
+        } else {
+            self.compose(&photo, frame_number, &edge_code)
+        };
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.film_rebate_config.film_stock"));
+                ui.add(egui::TextEdit::singleline(&mut self.film_stock).desired_width(160.0));
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.film_rebate_config.edge_code"));
+                template::update_ui(ui, &mut self.edge_code);
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.film_rebate_config.start_number"));
+                ui.add(egui::DragValue::new(&mut self.start_number).range(0..=99));
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.font"));
+                self.font.update_ui(ui, "film_rebate_font");
+            });
+            ui.add(egui::Slider::new(&mut self.font_size, 1.0..=100.0).text(t!("theme.font_size")))
+                .on_hover_text(t!(
+                    "theme.font_size_description",
+                    default = DEFAULT_FONT_SIZE
+                ));
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.film_rebate_config.rebate_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.rebate_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+                ui.label(t!("theme.film_rebate_config.hole_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.hole_color,
+                    egui::color_picker::Alpha::OnlyBlend,
+                );
+                ui.label(t!("theme.font_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.font_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+            self.effects.update_ui(ui, "film_rebate_text_effects");
+        });
+    }
+}
@@ -0,0 +1,336 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! White (or colored) border frame that grows the canvas,
+//! with EXIF caption on the thicker bottom margin
+
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::theme::{Theme, palette, sampler, template, text_dimensions, text_effects::TextEffects};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+const DEFAULT_FONT_SIZE: u32 = 25;
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum CaptionLayout {
+    /// Camera and lens on left, settings and datetime on right
+    Split,
+
+    /// Every line stacked on center
+    Centered,
+
+    /// Border only, no text
+    Hidden,
+}
+
+impl CaptionLayout {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            CaptionLayout::Split => t!("theme.frame_config.split"),
+            CaptionLayout::Centered => t!("theme.frame_config.centered"),
+            CaptionLayout::Hidden => t!("theme.frame_config.hidden"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct FrameLayout {
+    /// Left, right and top border in percent of short edge of photo
+    pub side_ratio: f32,
+
+    /// Bottom border in percent of short edge of photo
+    pub bottom_ratio: f32,
+
+    pub caption: CaptionLayout,
+}
+
+impl FrameLayout {
+    fn update_ui(&mut self, ui: &mut egui::Ui, id_salt: &str) {
+        ui.add(
+            egui::Slider::new(&mut self.side_ratio, 0.0..=15.0)
+                .suffix("%")
+                .text(t!("theme.frame_config.side_ratio")),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.bottom_ratio, 0.0..=40.0)
+                .suffix("%")
+                .text(t!("theme.frame_config.bottom_ratio")),
+        );
+        egui::ComboBox::from_id_salt(id_salt)
+            .selected_text(self.caption.label())
+            .show_ui(ui, |ui| {
+                for caption in CaptionLayout::iter() {
+                    ui.selectable_value(&mut self.caption, caption, caption.label());
+                }
+            });
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Frame {
+    horizontal: FrameLayout,
+    vertical: FrameLayout,
+    background: egui::Color32,
+    /// Use dominant color of photo as background, font colors follow it for contrast
+    palette_background: bool,
+    font_color: egui::Color32,
+    sub_font_color: egui::Color32,
+    font: FontChoice,
+    font_size: f32,
+    /// Outline, shadow and plate of caption
+    effects: TextEffects,
+    /// Shooting settings text, see `template`
+    settings: String,
+}
+
+impl core::default::Default for Frame {
+    fn default() -> Self {
+        Self {
+            horizontal: FrameLayout {
+                side_ratio: 3.0,
+                bottom_ratio: 12.0,
+                caption: CaptionLayout::Split,
+            },
+            vertical: FrameLayout {
+                side_ratio: 3.0,
+                bottom_ratio: 15.0,
+                caption: CaptionLayout::Centered,
+            },
+            background: egui::Color32::WHITE,
+            palette_background: false,
+            font_color: egui::Color32::from_gray(0x20),
+            sub_font_color: egui::Color32::from_gray(0x80),
+            font: FontChoice::Bundled(OverlayFont::Sans),
+            font_size: DEFAULT_FONT_SIZE as f32,
+            effects: TextEffects::default(),
+            settings: template::DEFAULT_SETTINGS.to_owned(),
+        }
+    }
+}
+
+impl Frame {
+    /// relative size on bottom band height
+    fn rel_size<F: Copy + num_traits::AsPrimitive<f32>>(&self, size: F, band: u32) -> f32 {
+        size.as_() * (self.font_size / (DEFAULT_FONT_SIZE as f32)) * (band as f32 / 1000.0)
+    }
+}
+
+impl Theme for Frame {
+    fn unique_name(&self) -> &'static str {
+        "frame"
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        t!("theme.frame")
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let exif = &pi.view_exif;
+        let dyn_image = pi.developed(export_config)?;
+        let (img_w, img_h) = (dyn_image.width(), dyn_image.height());
+
+        let layout = if img_h > img_w {
+            &self.vertical
+        } else {
+            &self.horizontal
+        };
+
+        let short = img_w.min(img_h) as f32;
+        let border = (short * layout.side_ratio / 100.0).round() as u32;
+        let band = (short * layout.bottom_ratio / 100.0).round() as u32;
+        let (canvas_w, canvas_h) = (img_w + border * 2, img_h + border + band);
+
+        let photo = dyn_image.to_rgba8();
+        let background = match self.palette_background {
+            true => palette::dominant_color(&photo),
+            false => None,
+        };
+        let mut canvas = image::RgbaImage::from_pixel(
+            canvas_w,
+            canvas_h,
+            background.unwrap_or(crate::theme::color32_to_rgba(self.background)),
+        );
+        image::imageops::overlay(&mut canvas, &photo, border as i64, border as i64);
+
+        let font = self.font.chain();
+        let [main_color, sub_color] = [self.font_color, self.sub_font_color].map(|color| {
+            let color = crate::theme::color32_to_rgba(color);
+            match background {
+                Some(background) => sampler::contrast_color(color, palette::luma(background)),
+                None => color,
+            }
+        });
+        let main_scale = ab_glyph::PxScale::from(self.rel_size(200, band));
+        let sub_scale = ab_glyph::PxScale::from(self.rel_size(150, band));
+        let line_gap = self.rel_size(40, band);
+        let band_top = (border + img_h) as f32;
+
+        let camera = format!("{}  {}", exif.camera_mnf, exif.camera_model)
+            .trim()
+            .to_owned();
+        let lens = exif.lens_model.trim().to_owned();
+        let settings = template::render(&self.settings, exif).trim().to_owned();
+
+        macro_rules! draw {
+            ($xxx:expr, $yyy:expr, $color:expr, $scale:expr, $text:expr) => {
+                self.effects.draw_text(
+                    &mut canvas,
+                    &font,
+                    $scale,
+                    ($xxx) as f32,
+                    ($yyy) as f32,
+                    $color,
+                    $text,
+                )
+            };
+        }
+
+        match layout.caption {
+            CaptionLayout::Split => {
+                let (_, main_h) = text_dimensions(main_scale, &font, "");
+                let (_, sub_h) = text_dimensions(sub_scale, &font, "");
+                let block_h = main_h + line_gap + sub_h;
+                let top = band_top + (band as f32 - block_h) / 2.0;
+                let sub_top = top + main_h + line_gap;
+
+                // Left
+                let (camera_w, _) = text_dimensions(main_scale, &font, &camera);
+                let (lens_w, _) = text_dimensions(sub_scale, &font, &lens);
+                let left_w = camera_w.max(lens_w);
+                self.effects.draw_plate(
+                    &mut canvas,
+                    border as f32,
+                    top,
+                    left_w,
+                    block_h,
+                    main_scale.y,
+                );
+                draw!(border, top, main_color, main_scale, &camera);
+                draw!(border, sub_top, sub_color, sub_scale, &lens);
+
+                // Right
+                let right = (canvas_w - border) as f32;
+                let (settings_w, _) = text_dimensions(main_scale, &font, &settings);
+                let (datetime_w, _) = text_dimensions(sub_scale, &font, &exif.datetime);
+                let right_w = settings_w.max(datetime_w);
+                self.effects.draw_plate(
+                    &mut canvas,
+                    right - right_w,
+                    top,
+                    right_w,
+                    block_h,
+                    main_scale.y,
+                );
+                draw!(right - settings_w, top, main_color, main_scale, &settings);
+                draw!(
+                    right - datetime_w,
+                    sub_top,
+                    sub_color,
+                    sub_scale,
+                    &exif.datetime
+                );
+            }
+            CaptionLayout::Centered => {
+                let lines = [
+                    (camera.as_str(), main_scale, main_color),
+                    (lens.as_str(), sub_scale, sub_color),
+                    (settings.as_str(), sub_scale, sub_color),
+                ]
+                .into_iter()
+                .filter(|(text, _, _)| !text.is_empty())
+                .map(|(text, scale, color)| {
+                    let (w, h) = text_dimensions(scale, &font, text);
+                    (text, scale, color, w, h)
+                })
+                .collect::<Vec<_>>();
+
+                let block_h = lines.iter().map(|line| line.4).sum::<f32>()
+                    + line_gap * lines.len().saturating_sub(1) as f32;
+                let mut y = band_top + (band as f32 - block_h) / 2.0;
+                let block_w = lines.iter().map(|line| line.3).fold(0.0, f32::max);
+                self.effects.draw_plate(
+                    &mut canvas,
+                    (canvas_w as f32 - block_w) / 2.0,
+                    y,
+                    block_w,
+                    block_h,
+                    main_scale.y,
+                );
+
+                for (text, scale, color, w, h) in lines {
+                    draw!((canvas_w as f32 - w) / 2.0, y, color, scale, text);
+                    y += h + line_gap;
+                }
+            }
+            CaptionLayout::Hidden => {}
+        }
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.label(t!("theme.horizontal"));
+            self.horizontal.update_ui(ui, "frame_horizontal_caption");
+            ui.add_space(1.0);
+
+            ui.label(t!("theme.vertical"));
+            self.vertical.update_ui(ui, "frame_vertical_caption");
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.settings_template"));
+                template::update_ui(ui, &mut self.settings);
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.font"));
+                self.font.update_ui(ui, "frame_font");
+            });
+
+            ui.add(egui::Slider::new(&mut self.font_size, 1.0..=100.0).text(t!("theme.font_size")))
+                .on_hover_text(t!(
+                    "theme.font_size_description",
+                    default = DEFAULT_FONT_SIZE
+                ));
+
+            ui.checkbox(
+                &mut self.palette_background,
+                t!("theme.frame_config.palette_background"),
+            );
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.background_color"));
+                ui.add_enabled_ui(!self.palette_background, |ui| {
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.background,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                });
+                ui.label(t!("theme.font_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.font_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+                ui.label(t!("theme.sub_font_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.sub_font_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+            self.effects.update_ui(ui, "frame_text_effects");
+        });
+    }
+}
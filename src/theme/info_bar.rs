@@ -0,0 +1,270 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Info bar appended below the photo.
+//! Maker logo and camera model on left, shooting settings and datetime on right.
+
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::theme::brand_logo::BrandLogo;
+use crate::theme::{Theme, template, text_dimensions, text_effects::TextEffects};
+use rust_i18n::t;
+
+const DEFAULT_FONT_SIZE: u32 = 25;
+/// Text is not shrunk below this scale to fit both left and right blocks
+const MIN_TEXT_FIT: f32 = 0.6;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct InfoBar {
+    /// Bar height in percent of short edge of photo
+    bar_ratio: f32,
+    /// Logo height in percent of bar height
+    logo_ratio: f32,
+    show_logo: bool,
+    background: egui::Color32,
+    font_color: egui::Color32,
+    sub_font_color: egui::Color32,
+    font: FontChoice,
+    font_size: f32,
+    /// Outline, shadow and plate of text
+    effects: TextEffects,
+    /// Shooting settings text, see `template`
+    settings: String,
+}
+
+impl core::default::Default for InfoBar {
+    fn default() -> Self {
+        Self {
+            bar_ratio: 11.0,
+            logo_ratio: 30.0,
+            show_logo: true,
+            background: egui::Color32::WHITE,
+            font_color: egui::Color32::from_gray(0x10),
+            sub_font_color: egui::Color32::from_gray(0x90),
+            font: FontChoice::Bundled(OverlayFont::Sans),
+            font_size: DEFAULT_FONT_SIZE as f32,
+            effects: TextEffects::default(),
+            settings: template::DEFAULT_SETTINGS.to_owned(),
+        }
+    }
+}
+
+impl InfoBar {
+    /// relative size on bar height
+    fn rel_size<F: Copy + num_traits::AsPrimitive<f32>>(&self, size: F, bar: u32) -> f32 {
+        size.as_() * (self.font_size / (DEFAULT_FONT_SIZE as f32)) * (bar as f32 / 1000.0)
+    }
+}
+
+impl Theme for InfoBar {
+    fn unique_name(&self) -> &'static str {
+        "info_bar"
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        t!("theme.info_bar")
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let exif = &pi.view_exif;
+        let dyn_image = pi.developed(export_config)?;
+        let (img_w, img_h) = (dyn_image.width(), dyn_image.height());
+
+        let bar = (img_w.min(img_h) as f32 * self.bar_ratio / 100.0).round() as u32;
+        let mut canvas = image::RgbaImage::from_pixel(
+            img_w,
+            img_h + bar,
+            crate::theme::color32_to_rgba(self.background),
+        );
+        image::imageops::overlay(&mut canvas, &dyn_image.to_rgba8(), 0, 0);
+
+        let font = self.font.chain();
+        let main_color = crate::theme::color32_to_rgba(self.font_color);
+        let sub_color = crate::theme::color32_to_rgba(self.sub_font_color);
+        let padding = (bar as f32 * 0.5).round();
+        let gap = (bar as f32 * 0.2).round();
+        let bar_top = img_h as f32;
+        let bar_center = bar_top + bar as f32 / 2.0;
+
+        macro_rules! draw {
+            ($xxx:expr, $yyy:expr, $color:expr, $scale:expr, $text:expr) => {
+                self.effects
+                    .draw_text(&mut canvas, &font, $scale, $xxx, $yyy, $color, $text)
+            };
+        }
+
+        // Left, logo or maker name as text fallback
+        let logo_mask = match BrandLogo::find(&exif.camera_mnf).filter(|_| self.show_logo) {
+            Some(logo) => {
+                let logo_h = (bar as f32 * self.logo_ratio / 100.0).round() as u32;
+                log::debug!("Draw brand logo of {}", logo.name);
+                Some(logo.mask(logo_h)?)
+            }
+            None => None,
+        };
+        let mnf = match logo_mask {
+            Some(_) => String::new(),
+            None => exif.camera_mnf.to_uppercase(),
+        };
+        // Right, settings on upper line and datetime on lower line
+        let settings = template::render(&self.settings, exif).trim().to_owned();
+
+        // Shrink text when left and right blocks overlap on narrow photo,
+        // drop right block when text would be too small to read
+        let (main_size, sub_size) = (self.rel_size(220, bar), self.rel_size(160, bar));
+        let measure =
+            |size: f32, text: &str| text_dimensions(ab_glyph::PxScale::from(size), &font, text).0;
+        let left_fixed = padding
+            + logo_mask.as_ref().map_or(0.0, |x| x.width() as f32 + gap)
+            + if mnf.is_empty() { 0.0 } else { gap };
+        let left_text = measure(main_size, &mnf) + measure(main_size, &exif.camera_model);
+        let right_text = measure(main_size, &settings).max(measure(sub_size, &exif.datetime));
+        let room = img_w as f32 - padding - left_fixed;
+
+        let both_fit = (room - 2.0 * gap) / (left_text + right_text);
+        let show_right = both_fit >= MIN_TEXT_FIT;
+        let fit = if show_right {
+            both_fit.min(1.0)
+        } else if left_text > 0.0 {
+            (room / left_text).clamp(MIN_TEXT_FIT, 1.0)
+        } else {
+            1.0
+        };
+        let main_scale = ab_glyph::PxScale::from(main_size * fit);
+        let sub_scale = ab_glyph::PxScale::from(sub_size * fit);
+
+        let (_, main_h) = text_dimensions(main_scale, &font, "");
+        let mut x = padding;
+
+        // Plate behind text only, logo is not text
+        let plate_x = padding + logo_mask.as_ref().map_or(0.0, |x| x.width() as f32 + gap);
+        let plate_w = left_fixed - plate_x + left_text * fit;
+        self.effects.draw_plate(
+            &mut canvas,
+            plate_x,
+            bar_center - main_h / 2.0,
+            plate_w,
+            main_h,
+            main_scale.y,
+        );
+
+        if let Some(mask) = &logo_mask {
+            crate::theme::blend_mask_mut(
+                &mut canvas,
+                mask,
+                x as i64,
+                (bar_center - mask.height() as f32 / 2.0).round() as i64,
+                main_color,
+            );
+            x += mask.width() as f32 + gap;
+        } else if !mnf.is_empty() {
+            let (mnf_w, _) = text_dimensions(main_scale, &font, &mnf);
+            draw!(x, bar_center - main_h / 2.0, main_color, main_scale, &mnf);
+            x += mnf_w + gap;
+        }
+
+        draw!(
+            x,
+            bar_center - main_h / 2.0,
+            main_color,
+            main_scale,
+            &exif.camera_model
+        );
+
+        if show_right {
+            let (settings_w, settings_h) = text_dimensions(main_scale, &font, &settings);
+            let (datetime_w, datetime_h) = text_dimensions(sub_scale, &font, &exif.datetime);
+            let block_w = settings_w.max(datetime_w);
+            let top = bar_center - (settings_h + datetime_h) / 2.0;
+            let left = img_w as f32 - padding - block_w;
+            self.effects.draw_plate(
+                &mut canvas,
+                left,
+                top,
+                block_w,
+                settings_h + datetime_h,
+                main_scale.y,
+            );
+
+            draw!(left, top, main_color, main_scale, &settings);
+            draw!(left, top + settings_h, sub_color, sub_scale, &exif.datetime);
+
+            // Thin divider between left and right
+            let divider_w = (bar as f32 * 0.01).round().max(1.0) as u32;
+            let divider_h = (bar as f32 * 0.5).round().max(1.0) as u32;
+            imageproc::drawing::draw_filled_rect_mut(
+                &mut canvas,
+                imageproc::rect::Rect::at(
+                    (left - gap) as i32,
+                    (bar_center - divider_h as f32 / 2.0) as i32,
+                )
+                .of_size(divider_w, divider_h),
+                sub_color,
+            );
+        }
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.add(
+                egui::Slider::new(&mut self.bar_ratio, 3.0..=30.0)
+                    .suffix("%")
+                    .text(t!("theme.info_bar_config.bar_ratio")),
+            );
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.settings_template"));
+                template::update_ui(ui, &mut self.settings);
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.font"));
+                self.font.update_ui(ui, "info_bar_font");
+            });
+            ui.add(egui::Slider::new(&mut self.font_size, 1.0..=100.0).text(t!("theme.font_size")))
+                .on_hover_text(t!(
+                    "theme.font_size_description",
+                    default = DEFAULT_FONT_SIZE
+                ));
+            ui.checkbox(&mut self.show_logo, t!("theme.info_bar_config.show_logo"))
+                .on_hover_text(t!("theme.info_bar_config.show_logo_description"));
+            ui.add_enabled(
+                self.show_logo,
+                egui::Slider::new(&mut self.logo_ratio, 10.0..=80.0)
+                    .suffix("%")
+                    .text(t!("theme.info_bar_config.logo_ratio")),
+            );
+
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.background_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.background,
+                    egui::color_picker::Alpha::Opaque,
+                );
+                ui.label(t!("theme.font_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.font_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+                ui.label(t!("theme.sub_font_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.sub_font_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+            self.effects.update_ui(ui, "info_bar_text_effects");
+        });
+    }
+}
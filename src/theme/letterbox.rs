@@ -0,0 +1,290 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Cinema aspect ratio picture between black bars,
+//! EXIF details on top bar and subtitle-style caption on bottom bar
+
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::theme::{Theme, effects, template, text_dimensions, text_effects::TextEffects};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+const DEFAULT_FONT_SIZE: u32 = 25;
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum CinemaAspect {
+    /// 2.39:1 anamorphic
+    Scope,
+    /// 1.85:1
+    Flat,
+    Custom,
+}
+
+impl CinemaAspect {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::Scope => "2.39:1".into(),
+            Self::Flat => "1.85:1".into(),
+            Self::Custom => t!("theme.letterbox_config.custom"),
+        }
+    }
+}
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum LetterboxFit {
+    /// Cut photo to aspect ratio, position from `PackedImage::crop_offset`
+    Crop,
+    /// Keep whole photo, fill rest of picture with bar color
+    Pad,
+}
+
+impl LetterboxFit {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::Crop => t!("theme.letterbox_config.crop"),
+            Self::Pad => t!("theme.letterbox_config.pad"),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Letterbox {
+    aspect: CinemaAspect,
+    /// width / height on `CinemaAspect::Custom`
+    custom_ratio: f32,
+    fit: LetterboxFit,
+    /// Height of each bar in percent of picture height
+    bar_ratio: f32,
+    bar_color: egui::Color32,
+    show_details: bool,
+    /// Top bar text, see `template`
+    details: String,
+    show_subtitle: bool,
+    /// Bottom bar text, see `template`
+    subtitle: String,
+    font_color: egui::Color32,
+    sub_font_color: egui::Color32,
+    font: FontChoice,
+    font_size: f32,
+    /// Outline, shadow and plate of text
+    effects: TextEffects,
+}
+
+impl core::default::Default for Letterbox {
+    fn default() -> Self {
+        Self {
+            aspect: CinemaAspect::Scope,
+            custom_ratio: 2.0,
+            fit: LetterboxFit::Crop,
+            bar_ratio: 12.0,
+            bar_color: egui::Color32::BLACK,
+            show_details: true,
+            details: format!(
+                "{{camera_mnf}} {{camera_model}}    {}",
+                template::DEFAULT_SETTINGS
+            ),
+            show_subtitle: true,
+            subtitle: "[{date}]".to_owned(),
+            font_color: egui::Color32::from_rgb(0xF5, 0xE6, 0x6E),
+            sub_font_color: egui::Color32::from_gray(0x90),
+            font: FontChoice::Bundled(OverlayFont::Sans),
+            font_size: DEFAULT_FONT_SIZE as f32,
+            effects: TextEffects::default(),
+        }
+    }
+}
+
+impl Letterbox {
+    /// width / height of picture
+    fn ratio(&self) -> f32 {
+        match self.aspect {
+            CinemaAspect::Scope => 2.39,
+            CinemaAspect::Flat => 1.85,
+            CinemaAspect::Custom => self.custom_ratio.clamp(0.2, 5.0),
+        }
+    }
+
+    /// relative size on picture height
+    fn rel_size<F: Copy + num_traits::AsPrimitive<f32>>(&self, size: F, picture_h: u32) -> f32 {
+        size.as_() * (self.font_size / (DEFAULT_FONT_SIZE as f32)) * (picture_h as f32 / 1000.0)
+    }
+}
+
+impl Theme for Letterbox {
+    fn unique_name(&self) -> &'static str {
+        "letterbox"
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        t!("theme.letterbox")
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let exif = &pi.view_exif;
+        let bar_color = crate::theme::color32_to_rgba(self.bar_color);
+        let ratio = self.ratio();
+        let photo = pi
+            .developed_with_crop(export_config, |photo| match self.fit {
+                LetterboxFit::Crop => effects::crop_to_aspect(&photo, ratio, pi.crop_offset),
+                LetterboxFit::Pad => photo,
+            })?
+            .to_rgba8();
+
+        let picture = match self.fit {
+            LetterboxFit::Crop => photo,
+            LetterboxFit::Pad => {
+                let (img_w, img_h) = photo.dimensions();
+                let (pic_w, pic_h) = if img_w as f32 / img_h as f32 > ratio {
+                    (img_w, (img_w as f32 / ratio).round() as u32)
+                } else {
+                    ((img_h as f32 * ratio).round() as u32, img_h)
+                };
+                let mut picture = image::RgbaImage::from_pixel(pic_w, pic_h, bar_color);
+                image::imageops::overlay(
+                    &mut picture,
+                    &photo,
+                    ((pic_w - img_w) / 2) as i64,
+                    ((pic_h - img_h) / 2) as i64,
+                );
+                picture
+            }
+        };
+
+        let (pic_w, pic_h) = picture.dimensions();
+        let bar = (pic_h as f32 * self.bar_ratio / 100.0).round() as u32;
+        let mut canvas = image::RgbaImage::from_pixel(pic_w, pic_h + bar * 2, bar_color);
+        image::imageops::overlay(&mut canvas, &picture, 0, bar as i64);
+
+        let font = self.font.chain();
+        let centered = |canvas: &mut image::RgbaImage,
+                        band_top: f32,
+                        scale: ab_glyph::PxScale,
+                        color: image::Rgba<u8>,
+                        text: &str| {
+            if text.is_empty() {
+                return;
+            }
+            let (w, h) = text_dimensions(scale, &font, text);
+            let (x, y) = ((pic_w as f32 - w) / 2.0, band_top + (bar as f32 - h) / 2.0);
+            self.effects.draw_plate(canvas, x, y, w, h, scale.y);
+            self.effects
+                .draw_text(canvas, &font, scale, x, y, color, text);
+        };
+
+        if self.show_details {
+            let details = template::render(&self.details, exif).trim().to_owned();
+            centered(
+                &mut canvas,
+                0.0,
+                ab_glyph::PxScale::from(self.rel_size(30, pic_h)),
+                crate::theme::color32_to_rgba(self.sub_font_color),
+                &details,
+            );
+        }
+
+        if self.show_subtitle {
+            let subtitle = template::render(&self.subtitle, exif).trim().to_owned();
+            centered(
+                &mut canvas,
+                (bar + pic_h) as f32,
+                ab_glyph::PxScale::from(self.rel_size(45, pic_h)),
+                crate::theme::color32_to_rgba(self.font_color),
+                &subtitle,
+            );
+        }
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.letterbox_config.aspect"));
+                egui::ComboBox::from_id_salt("letterbox_aspect")
+                    .selected_text(self.aspect.label())
+                    .show_ui(ui, |ui| {
+                        for aspect in CinemaAspect::iter() {
+                            ui.selectable_value(&mut self.aspect, aspect, aspect.label());
+                        }
+                    });
+                if self.aspect == CinemaAspect::Custom {
+                    ui.add(
+                        egui::DragValue::new(&mut self.custom_ratio)
+                            .range(0.2..=5.0)
+                            .speed(0.01)
+                            .suffix(":1"),
+                    );
+                }
+                egui::ComboBox::from_id_salt("letterbox_fit")
+                    .selected_text(self.fit.label())
+                    .show_ui(ui, |ui| {
+                        for fit in LetterboxFit::iter() {
+                            ui.selectable_value(&mut self.fit, fit, fit.label());
+                        }
+                    });
+            });
+            ui.add(
+                egui::Slider::new(&mut self.bar_ratio, 0.0..=40.0)
+                    .suffix("%")
+                    .text(t!("theme.letterbox_config.bar_ratio")),
+            );
+
+            ui.checkbox(
+                &mut self.show_details,
+                t!("theme.letterbox_config.show_details"),
+            );
+            ui.add_enabled_ui(self.show_details, |ui| {
+                template::update_ui(ui, &mut self.details);
+            });
+            ui.checkbox(
+                &mut self.show_subtitle,
+                t!("theme.letterbox_config.show_subtitle"),
+            );
+            ui.add_enabled_ui(self.show_subtitle, |ui| {
+                template::update_ui(ui, &mut self.subtitle);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.font"));
+                self.font.update_ui(ui, "letterbox_font");
+            });
+            ui.add(egui::Slider::new(&mut self.font_size, 1.0..=100.0).text(t!("theme.font_size")))
+                .on_hover_text(t!(
+                    "theme.font_size_description",
+                    default = DEFAULT_FONT_SIZE
+                ));
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.background_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.bar_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+                ui.label(t!("theme.font_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.font_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+                ui.label(t!("theme.sub_font_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.sub_font_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+            self.effects.update_ui(ui, "letterbox_text_effects");
+        });
+    }
+}
@@ -7,17 +7,319 @@
 
 //! collection of themes
 
+pub(crate) mod anchor;
+pub(crate) mod blur_canvas;
+pub(crate) mod brand_logo;
+pub(crate) mod custom;
+pub(crate) mod date_stamp;
+pub(crate) mod effects;
 pub(crate) mod film;
+pub(crate) mod film_rebate;
+pub(crate) mod frame;
+pub(crate) mod info_bar;
+pub(crate) mod letterbox;
 pub(crate) mod nothing;
+pub(crate) mod palette;
+pub(crate) mod palette_strip;
+pub(crate) mod sampler;
+pub(crate) mod script;
+pub(crate) mod showcase;
+pub(crate) mod template;
+pub(crate) mod text_effects;
+pub(crate) mod watermark;
+use ab_glyph::PxScale;
 use rust_i18n::t;
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
 
+/// `Color32` is premultiplied, image pixels are not
 pub fn color32_to_rgba(color: egui::Color32) -> image::Rgba<u8> {
-    let [r, g, b, a] = color.to_array();
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
     image::Rgba([r, g, b, a])
 }
 
+/// Parse "#rrggbb" or "#rrggbbaa"
+pub(crate) fn parse_hex_color(text: &str) -> Option<image::Rgba<u8>> {
+    let hex = text.trim().trim_start_matches('#');
+    if !hex.is_ascii() || !matches!(hex.len(), 6 | 8) {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok();
+    let alpha = if hex.len() == 8 { channel(3)? } else { 255 };
+    Some(image::Rgba([channel(0)?, channel(1)?, channel(2)?, alpha]))
+}
+
+lazy_static::lazy_static! {
+    static ref INTERNED: std::sync::Mutex<std::collections::HashSet<&'static str>> =
+        std::sync::Mutex::new(std::collections::HashSet::new());
+}
+
+/// Static name for `Theme::unique_name` of loaded theme,
+/// same name is leaked only once however many times themes are reloaded
+pub(crate) fn intern(name: String) -> &'static str {
+    let mut interned = INTERNED.lock().unwrap();
+    match interned.get(name.as_str()) {
+        Some(x) => x,
+        None => {
+            let leaked: &'static str = Box::leak(name.into_boxed_str());
+            interned.insert(leaked);
+            leaked
+        }
+    }
+}
+
+/// "#rrggbb" or "#rrggbbaa" in JSON of custom theme and text effects
+#[derive(Clone, Copy)]
+pub(crate) struct HexColor(pub image::Rgba<u8>);
+
+impl<'de> Deserialize<'de> for HexColor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        parse_hex_color(&text).map(Self).ok_or_else(|| {
+            serde::de::Error::custom(format!(
+                "invalid color {text:?}, expected \"#rrggbb\" or \"#rrggbbaa\""
+            ))
+        })
+    }
+}
+
+impl Serialize for HexColor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [r, g, b, a] = self.0.0;
+        let text = if a == 255 {
+            format!("#{r:02x}{g:02x}{b:02x}")
+        } else {
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+        };
+        serializer.serialize_str(&text)
+    }
+}
+
+/// `#[serde(with = "crate::theme::hex_color32")]` for `Color32` field written as `HexColor`
+pub(crate) mod hex_color32 {
+    use super::HexColor;
+    use serde::{Deserialize, Serialize};
+
+    pub fn serialize<S: serde::Serializer>(
+        color: &egui::Color32,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        HexColor(super::color32_to_rgba(*color)).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<egui::Color32, D::Error> {
+        let [r, g, b, a] = HexColor::deserialize(deserializer)?.0.0;
+        Ok(egui::Color32::from_rgba_unmultiplied(r, g, b, a))
+    }
+}
+
+/// Measure width and height of single line text with given scale
+pub(crate) fn text_dimensions(
+    scale: PxScale,
+    font: &crate::fonts::FontChain,
+    text: &str,
+) -> (f32, f32) {
+    font.dimensions(scale, text)
+}
+
+/// Render single line text into coverage mask with transparent padding around
+pub(crate) fn text_mask(
+    scale: PxScale,
+    font: &crate::fonts::FontChain,
+    text: &str,
+    padding: u32,
+) -> image::GrayImage {
+    let (text_w, text_h) = text_dimensions(scale, font, text);
+    let mut mask = image::GrayImage::new(
+        text_w.ceil() as u32 + padding * 2,
+        text_h.ceil() as u32 + padding * 2,
+    );
+    font.draw_mut(
+        &mut mask,
+        image::Luma([255]),
+        padding as i32,
+        padding as i32,
+        scale,
+        text,
+    );
+    mask
+}
+
+/// Load PNG/SVG or other image file and resize it to given height with aspect ratio
+pub(crate) fn load_image_with_height(
+    path: &std::path::Path,
+    height: u32,
+) -> Result<image::RgbaImage, image::ImageError> {
+    let height = height.max(1);
+    let decoding_error = |e: String| {
+        image::ImageError::Decoding(image::error::DecodingError::new(
+            image::error::ImageFormatHint::PathExtension(path.to_path_buf()),
+            e,
+        ))
+    };
+
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+    {
+        use resvg::{tiny_skia, usvg};
+
+        let data = std::fs::read(path)?;
+        let tree = usvg::Tree::from_data(&data, &usvg::Options::default())
+            .map_err(|e| decoding_error(format!("svg parse failed by {e:?}")))?;
+        let size = tree.size();
+        let scale = height as f32 / size.height();
+        let width = (size.width() * scale).ceil().max(1.0) as u32;
+
+        let mut pixmap = tiny_skia::Pixmap::new(width, height)
+            .ok_or_else(|| decoding_error(format!("svg pixmap {width}x{height} failed")))?;
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+
+        let pixels = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|px| {
+                let c = px.demultiply();
+                [c.red(), c.green(), c.blue(), c.alpha()]
+            })
+            .collect::<Vec<u8>>();
+
+        image::RgbaImage::from_raw(width, height, pixels)
+            .ok_or_else(|| decoding_error("svg buffer size mismatch".to_owned()))
+    } else {
+        let image = image::open(path)?.to_rgba8();
+        let width = ((image.width() as f32 * height as f32) / image.height() as f32)
+            .round()
+            .max(1.0) as u32;
+
+        Ok(image::imageops::resize(
+            &image,
+            width,
+            height,
+            image::imageops::FilterType::Lanczos3,
+        ))
+    }
+}
+
+/// Blend single color onto canvas through coverage mask
+pub(crate) fn blend_mask_mut(
+    canvas: &mut image::RgbaImage,
+    mask: &image::GrayImage,
+    x: i64,
+    y: i64,
+    color: image::Rgba<u8>,
+) {
+    use image::Pixel;
+
+    let (canvas_w, canvas_h) = (canvas.width() as i64, canvas.height() as i64);
+
+    for (mx, my, px) in mask.enumerate_pixels() {
+        let (cx, cy) = (x + mx as i64, y + my as i64);
+        if !(0..canvas_w).contains(&cx) || !(0..canvas_h).contains(&cy) {
+            continue;
+        }
+
+        let alpha = (color[3] as u32 * px[0] as u32 / 255) as u8;
+        if alpha == 0 {
+            continue;
+        }
+
+        canvas
+            .get_pixel_mut(cx as u32, cy as u32)
+            .blend(&image::Rgba([color[0], color[1], color[2], alpha]));
+    }
+}
+
+/// Per user folder for themes, fonts and other resources, e.g. `~/.config/chama-optics/themes`
+pub(crate) fn user_folder(name: &str) -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|x| x.join("chama-optics").join(name))
+}
+
+/// Load every `*.json` and `*.rhai` in themes folder, return themes and error messages
+fn custom_themes() -> (Vec<Arc<RwLock<dyn Theme>>>, Vec<String>) {
+    let Some(folder) = user_folder("themes") else {
+        return (vec![], vec![]);
+    };
+
+    let Ok(entries) = std::fs::read_dir(&folder) else {
+        log::info!("No themes folder {}", folder.display());
+        return (vec![], vec![]);
+    };
+
+    let mut paths = entries
+        .filter_map(|x| x.ok().map(|x| x.path()))
+        .filter(|x| {
+            x.extension().is_some_and(|ext| {
+                ext.eq_ignore_ascii_case("json") || ext.eq_ignore_ascii_case("rhai")
+            })
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut themes = Vec::new();
+    let mut errors = Vec::new();
+
+    for path in paths {
+        let loaded = if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("rhai"))
+        {
+            script::ScriptTheme::load(&path)
+                .map(|x| Arc::new(RwLock::new(x)) as Arc<RwLock<dyn Theme>>)
+        } else {
+            custom::CustomTheme::load(&path)
+                .map(|x| Arc::new(RwLock::new(x)) as Arc<RwLock<dyn Theme>>)
+        };
+
+        match loaded {
+            Ok(theme) => {
+                let name = theme.read().unwrap().unique_name();
+                let duplicated = themes
+                    .iter()
+                    .any(|t: &Arc<RwLock<dyn Theme>>| t.read().unwrap().unique_name() == name);
+                if duplicated {
+                    let e = format!("{}: theme name {:?} is duplicated", path.display(), name);
+                    log::error!("{e}");
+                    errors.push(e);
+                } else {
+                    log::info!("Loaded theme {}", path.display());
+                    themes.push(theme);
+                }
+            }
+            Err(e) => {
+                log::error!("{e}");
+                errors.push(e);
+            }
+        }
+    }
+
+    (themes, errors)
+}
+
+/// Every built-in theme, in default order
+fn builtin_themes() -> Vec<Arc<RwLock<dyn Theme>>> {
+    vec![
+        Arc::new(RwLock::new(film::Film::default())) as Arc<RwLock<dyn Theme>>,
+        Arc::new(RwLock::new(date_stamp::DateStamp::default())) as Arc<RwLock<dyn Theme>>,
+        Arc::new(RwLock::new(frame::Frame::default())) as Arc<RwLock<dyn Theme>>,
+        Arc::new(RwLock::new(film_rebate::FilmRebate::default())) as Arc<RwLock<dyn Theme>>,
+        Arc::new(RwLock::new(info_bar::InfoBar::default())) as Arc<RwLock<dyn Theme>>,
+        Arc::new(RwLock::new(blur_canvas::BlurCanvas::default())) as Arc<RwLock<dyn Theme>>,
+        Arc::new(RwLock::new(showcase::Showcase::default())) as Arc<RwLock<dyn Theme>>,
+        Arc::new(RwLock::new(letterbox::Letterbox::default())) as Arc<RwLock<dyn Theme>>,
+        Arc::new(RwLock::new(palette_strip::PaletteStrip::default())) as Arc<RwLock<dyn Theme>>,
+        Arc::new(RwLock::new(watermark::Watermark::default())) as Arc<RwLock<dyn Theme>>,
+        Arc::new(RwLock::new(nothing::Nothing::default())) as Arc<RwLock<dyn Theme>>,
+    ]
+}
+
 pub trait Theme {
     /// return unique name of theme
     fn unique_name(&self) -> &'static str;
@@ -48,6 +350,9 @@ pub struct ThemeRegistry {
     #[serde(skip)]
     pub themes: Vec<Arc<RwLock<dyn Theme>>>,
     pub selected: usize,
+    /// Errors while loading themes folder
+    #[serde(skip)]
+    pub errors: Vec<String>,
 }
 
 impl Default for ThemeRegistry {
@@ -58,21 +363,21 @@ impl Default for ThemeRegistry {
 
 impl ThemeRegistry {
     pub fn new() -> Self {
-        let film = Arc::new(RwLock::new(film::Film::default())) as Arc<RwLock<dyn Theme>>;
-        let nothing_theme =
-            Arc::new(RwLock::new(nothing::Nothing::default())) as Arc<RwLock<dyn Theme>>;
+        let (custom, errors) = custom_themes();
+        let mut themes = builtin_themes();
+        themes.extend(custom);
 
         Self {
-            themes: vec![film, nothing_theme],
+            themes,
             selected: 0,
+            errors,
         }
     }
 
     pub fn from_state(state: ThemeRegistryState) -> Self {
-        let available: Vec<Arc<RwLock<dyn Theme>>> = vec![
-            Arc::new(RwLock::new(film::Film::default())) as Arc<RwLock<dyn Theme>>,
-            Arc::new(RwLock::new(nothing::Nothing::default())) as Arc<RwLock<dyn Theme>>,
-        ];
+        let (custom, errors) = custom_themes();
+        let mut available = builtin_themes();
+        available.extend(custom);
 
         let mut ordered = Vec::new();
         let mut remaining = available.clone();
@@ -91,9 +396,33 @@ impl ThemeRegistry {
         Self {
             themes: ordered,
             selected,
+            errors,
         }
     }
 
+    /// Replace custom themes with files in themes folder, keep current selection if possible
+    pub fn reload_custom_themes(&mut self) {
+        let selected_name = self.themes[self.selected].read().unwrap().unique_name();
+
+        self.themes.retain(|t| {
+            !t.read()
+                .unwrap()
+                .unique_name()
+                .starts_with(custom::UNIQUE_NAME_PREFIX)
+        });
+
+        crate::fonts::reload_user_fonts();
+        let (custom, errors) = custom_themes();
+        self.themes.extend(custom);
+        self.errors = errors;
+
+        self.selected = self
+            .themes
+            .iter()
+            .position(|t| t.read().unwrap().unique_name() == selected_name)
+            .unwrap_or(0);
+    }
+
     pub fn to_state(&self) -> ThemeRegistryState {
         ThemeRegistryState {
             names: self
@@ -128,6 +457,26 @@ impl ThemeRegistry {
             ui.collapsing(t!("theme.settings"), |ui| {
                 self.themes[self.selected].write().unwrap().ui_config(ui);
             });
+
+            ui.collapsing(t!("theme.custom_themes"), |ui| {
+                if let Some(folder) = user_folder("themes") {
+                    ui.horizontal(|ui| {
+                        ui.label(t!("theme.themes_folder"));
+                        ui.monospace(folder.display().to_string());
+                    });
+
+                    if ui.button(t!("theme.reload_themes")).clicked() {
+                        if let Err(e) = std::fs::create_dir_all(&folder) {
+                            log::error!("Failed to create folder {}: {e}", folder.display());
+                        }
+                        self.reload_custom_themes();
+                    }
+                }
+
+                for e in &self.errors {
+                    ui.colored_label(ui.visuals().error_fg_color, e);
+                }
+            });
         });
     }
 }
@@ -25,8 +25,7 @@ impl Theme for Nothing {
         export_config: &crate::export_config::ExportConfig,
         output_path: &std::path::Path,
     ) -> Result<(), image::ImageError> {
-        let scale_config = &export_config.scale_config;
-        let dyn_image = pi.with_scale_and_orientation(*scale_config)?;
+        let dyn_image = pi.developed(export_config)?;
 
         export_config
             .output_format
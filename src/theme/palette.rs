@@ -0,0 +1,106 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Dominant colors of photo by median cut over downscaled copy.
+//! Box is cut at middle of its widest channel rather than median pixel,
+//! so flat area stays in one box and population of box means dominance.
+//! Result is deterministic, so same photo always gets same palette.
+
+use image::{Rgba, RgbaImage};
+
+/// Long edge of downscaled copy
+const SAMPLE_SIZE: u32 = 128;
+
+/// Channel with widest range, its minimum and its range
+fn widest_channel(pixels: &[[u8; 3]]) -> (usize, u8, u8) {
+    (0..3)
+        .map(|c| {
+            let (min, max) = pixels.iter().fold((u8::MAX, u8::MIN), |(min, max), px| {
+                (min.min(px[c]), max.max(px[c]))
+            });
+            (c, min, max.saturating_sub(min))
+        })
+        .max_by_key(|(_, _, range)| *range)
+        .unwrap_or((0, 0, 0))
+}
+
+fn average(pixels: &[[u8; 3]]) -> Rgba<u8> {
+    let len = pixels.len().max(1) as u64;
+    let sum = pixels.iter().fold([0u64; 3], |mut sum, px| {
+        for c in 0..3 {
+            sum[c] += px[c] as u64;
+        }
+        sum
+    });
+    let [r, g, b] = sum.map(|x| ((x + len / 2) / len) as u8);
+    Rgba([r, g, b, 255])
+}
+
+/// Up to `count` dominant colors, most common first.
+/// Transparent pixels are ignored, empty when image has no opaque pixel
+pub(crate) fn dominant_colors(image: &RgbaImage, count: usize) -> Vec<Rgba<u8>> {
+    let (w, h) = image.dimensions();
+    let ratio = (SAMPLE_SIZE as f32 / w.max(h).max(1) as f32).min(1.0);
+    let small = image::imageops::thumbnail(
+        image,
+        ((w as f32 * ratio).round() as u32).max(1),
+        ((h as f32 * ratio).round() as u32).max(1),
+    );
+
+    let pixels = small
+        .pixels()
+        .filter(|px| px[3] >= 128)
+        .map(|px| [px[0], px[1], px[2]])
+        .collect::<Vec<_>>();
+    if pixels.is_empty() || count == 0 {
+        return Vec::new();
+    }
+
+    let mut boxes = vec![pixels];
+    while boxes.len() < count {
+        // Split box that is large and colorful first
+        let Some((idx, channel, middle)) = boxes
+            .iter()
+            .enumerate()
+            .map(|(idx, pixels)| {
+                let (channel, min, range) = widest_channel(pixels);
+                (idx, channel, min + range / 2, range as usize * pixels.len())
+            })
+            .filter(|(_, _, _, score)| *score > 0)
+            .max_by_key(|(_, _, _, score)| *score)
+            .map(|(idx, channel, middle, _)| (idx, channel, middle))
+        else {
+            break;
+        };
+
+        // Both are not empty since range is not zero
+        let (lower, upper) = boxes
+            .swap_remove(idx)
+            .into_iter()
+            .partition(|px| px[channel] <= middle);
+        boxes.push(lower);
+        boxes.push(upper);
+    }
+
+    boxes.sort_by_key(|pixels| std::cmp::Reverse(pixels.len()));
+    boxes.iter().map(|pixels| average(pixels)).collect()
+}
+
+/// Most common color of image
+pub(crate) fn dominant_color(image: &RgbaImage) -> Option<Rgba<u8>> {
+    // Few boxes keep large area apart from small vivid details
+    dominant_colors(image, 4).first().copied()
+}
+
+/// Perceived brightness in 0.0 ..= 1.0, same scale as `ContentSampler::luma_stats`
+pub(crate) fn luma(color: Rgba<u8>) -> f32 {
+    (0.2126 * color[0] as f32 + 0.7152 * color[1] as f32 + 0.0722 * color[2] as f32) / 255.0
+}
+
+/// "#RRGGBB"
+pub(crate) fn hex(color: Rgba<u8>) -> String {
+    format!("#{:02X}{:02X}{:02X}", color[0], color[1], color[2])
+}
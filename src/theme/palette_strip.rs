@@ -0,0 +1,240 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Border frame with strip of dominant colors below photo, and EXIF caption under the strip
+
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::theme::{Theme, palette, sampler, template, text_dimensions, text_effects::TextEffects};
+use rust_i18n::t;
+
+const DEFAULT_FONT_SIZE: u32 = 25;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct PaletteStrip {
+    /// Number of swatches, 5 to 8
+    count: usize,
+    /// Border around photo in percent of short edge of photo
+    border: f32,
+    /// Height of swatches in percent of short edge of photo
+    strip_ratio: f32,
+    /// Gap between swatches in percent of short edge of photo
+    spacing: f32,
+    show_hex: bool,
+    show_exif: bool,
+    background: egui::Color32,
+    font_color: egui::Color32,
+    font: FontChoice,
+    font_size: f32,
+    /// Outline, shadow and plate of hex codes and caption
+    effects: TextEffects,
+    /// Shooting settings text, see `template`
+    settings: String,
+}
+
+impl core::default::Default for PaletteStrip {
+    fn default() -> Self {
+        Self {
+            count: 6,
+            border: 3.0,
+            strip_ratio: 8.0,
+            spacing: 0.0,
+            show_hex: true,
+            show_exif: true,
+            background: egui::Color32::WHITE,
+            font_color: egui::Color32::from_gray(0x20),
+            font: FontChoice::Bundled(OverlayFont::Sans),
+            font_size: DEFAULT_FONT_SIZE as f32,
+            effects: TextEffects::default(),
+            settings: template::DEFAULT_SETTINGS.to_owned(),
+        }
+    }
+}
+
+impl PaletteStrip {
+    /// relative size on short edge of photo
+    fn rel_size<F: Copy + num_traits::AsPrimitive<f32>>(&self, size: F, short: u32) -> f32 {
+        size.as_() * (self.font_size / (DEFAULT_FONT_SIZE as f32)) * (short as f32 / 1000.0)
+    }
+}
+
+impl Theme for PaletteStrip {
+    fn unique_name(&self) -> &'static str {
+        "palette_strip"
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        t!("theme.palette_strip")
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let exif = &pi.view_exif;
+        let photo = pi.developed(export_config)?.to_rgba8();
+        let (img_w, img_h) = photo.dimensions();
+        let short = img_w.min(img_h);
+        let colors = palette::dominant_colors(&photo, self.count.clamp(5, 8));
+
+        let font = self.font.chain();
+        let font_color = crate::theme::color32_to_rgba(self.font_color);
+        let main_scale = ab_glyph::PxScale::from(self.rel_size(36, short));
+        let sub_scale = ab_glyph::PxScale::from(self.rel_size(26, short));
+        let hex_scale = ab_glyph::PxScale::from(self.rel_size(20, short));
+
+        let camera = format!("{}  {}", exif.camera_mnf, exif.camera_model)
+            .trim()
+            .to_owned();
+        let settings = template::render(&self.settings, exif).trim().to_owned();
+        let caption = [(camera, main_scale), (settings, sub_scale)]
+            .into_iter()
+            .filter(|(text, _)| self.show_exif && !text.is_empty())
+            .map(|(text, scale)| {
+                let (w, h) = text_dimensions(scale, &font, &text);
+                (text, scale, w, h)
+            })
+            .collect::<Vec<_>>();
+
+        let unit = short as f32 / 100.0;
+        let border = (self.border * unit).round() as u32;
+        let strip_h = (self.strip_ratio * unit).round().max(1.0) as u32;
+        let line_gap = self.rel_size(8, short);
+        let caption_h = caption.iter().map(|x| x.3).sum::<f32>()
+            + line_gap * caption.len().saturating_sub(1) as f32;
+        // Gap between strip and caption is same as border
+        let caption_band = if caption.is_empty() {
+            0
+        } else {
+            caption_h.ceil() as u32 + border
+        };
+
+        let (canvas_w, canvas_h) = (
+            img_w + border * 2,
+            img_h + strip_h + caption_band + border * 3,
+        );
+        let mut canvas = image::RgbaImage::from_pixel(
+            canvas_w,
+            canvas_h,
+            crate::theme::color32_to_rgba(self.background),
+        );
+        image::imageops::overlay(&mut canvas, &photo, border as i64, border as i64);
+
+        // Swatches share width of photo
+        let strip_top = border + img_h + border;
+        let spacing = self.spacing * unit;
+        let swatch_w = if colors.is_empty() {
+            0.0
+        } else {
+            (img_w as f32 - spacing * (colors.len() - 1) as f32) / colors.len() as f32
+        };
+
+        for (idx, color) in colors.iter().enumerate() {
+            let x0 = border as f32 + (swatch_w + spacing) * idx as f32;
+            let (x0, x1) = (x0.round() as u32, (x0 + swatch_w).round() as u32);
+            let swatch = image::RgbaImage::from_pixel(x1.saturating_sub(x0), strip_h, *color);
+            image::imageops::replace(&mut canvas, &swatch, x0 as i64, strip_top as i64);
+
+            if self.show_hex {
+                let text = palette::hex(*color);
+                let (text_w, text_h) = text_dimensions(hex_scale, &font, &text);
+                let text_color = sampler::contrast_color(font_color, palette::luma(*color));
+                let (x, y) = (
+                    x0 as f32 + (x1.saturating_sub(x0) as f32 - text_w) / 2.0,
+                    strip_top as f32 + strip_h as f32 - text_h * 1.4,
+                );
+                self.effects
+                    .draw_plate(&mut canvas, x, y, text_w, text_h, hex_scale.y);
+                self.effects
+                    .draw_text(&mut canvas, &font, hex_scale, x, y, text_color, &text);
+            }
+        }
+
+        let mut y = (strip_top + strip_h + border) as f32;
+        let caption_w = caption.iter().map(|x| x.2).fold(0.0, f32::max);
+        self.effects.draw_plate(
+            &mut canvas,
+            (canvas_w as f32 - caption_w) / 2.0,
+            y,
+            caption_w,
+            caption_h,
+            main_scale.y,
+        );
+        for (text, scale, w, h) in &caption {
+            let x = (canvas_w as f32 - w) / 2.0;
+            self.effects
+                .draw_text(&mut canvas, &font, *scale, x, y, font_color, text);
+            y += h + line_gap;
+        }
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.add(
+                egui::Slider::new(&mut self.count, 5..=8)
+                    .text(t!("theme.palette_strip_config.count")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.border, 0.0..=15.0)
+                    .suffix("%")
+                    .text(t!("theme.palette_strip_config.border")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.strip_ratio, 2.0..=30.0)
+                    .suffix("%")
+                    .text(t!("theme.palette_strip_config.strip_ratio")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.spacing, 0.0..=5.0)
+                    .suffix("%")
+                    .text(t!("theme.palette_strip_config.spacing")),
+            );
+            ui.checkbox(
+                &mut self.show_hex,
+                t!("theme.palette_strip_config.show_hex"),
+            );
+            ui.checkbox(
+                &mut self.show_exif,
+                t!("theme.palette_strip_config.show_exif"),
+            );
+            ui.add_enabled_ui(self.show_exif, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme.settings_template"));
+                    template::update_ui(ui, &mut self.settings);
+                });
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.font"));
+                self.font.update_ui(ui, "palette_strip_font");
+            });
+            ui.add(egui::Slider::new(&mut self.font_size, 1.0..=100.0).text(t!("theme.font_size")))
+                .on_hover_text(t!(
+                    "theme.font_size_description",
+                    default = DEFAULT_FONT_SIZE
+                ));
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.background_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.background,
+                    egui::color_picker::Alpha::Opaque,
+                );
+                ui.label(t!("theme.font_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.font_color,
+                    egui::color_picker::Alpha::Opaque,
+                );
+            });
+            self.effects.update_ui(ui, "palette_strip_text_effects");
+        });
+    }
+}
@@ -0,0 +1,137 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Sample photo under overlay to choose readable text color and quiet place automatically.
+//! Statistics are measured on small grayscale copy, so cost is same on any `ScaleConfig`.
+
+use image::{GrayImage, Luma, RgbaImage};
+
+/// Long edge of grayscale copy
+const SAMPLE_SIZE: u32 = 320;
+
+/// Minimum contrast ratio to keep preferred color, WCAG for large text
+const MIN_CONTRAST: f32 = 3.0;
+
+/// Dark color when preferred color has not enough contrast
+const DARK: image::Rgba<u8> = image::Rgba([24, 24, 24, 255]);
+/// Light color when preferred color has not enough contrast
+const LIGHT: image::Rgba<u8> = image::Rgba([255, 255, 255, 255]);
+
+pub(crate) struct ContentSampler {
+    luma: GrayImage,
+    /// Sobel gradient magnitude of `luma`
+    gradient: image::ImageBuffer<Luma<u16>, Vec<u16>>,
+    /// Size of sample over size of original
+    ratio: f32,
+}
+
+impl ContentSampler {
+    pub fn new(image: &RgbaImage) -> Self {
+        let (w, h) = image.dimensions();
+        let ratio = (SAMPLE_SIZE as f32 / w.max(h).max(1) as f32).min(1.0);
+        let small = image::imageops::thumbnail(
+            image,
+            ((w as f32 * ratio).round() as u32).max(1),
+            ((h as f32 * ratio).round() as u32).max(1),
+        );
+        let luma = image::DynamicImage::ImageRgba8(small).to_luma8();
+        let gradient = imageproc::gradients::sobel_gradients(&luma);
+
+        Self {
+            luma,
+            gradient,
+            ratio,
+        }
+    }
+
+    /// Rect on original image to (x0, y0, x1, y1) on sample, empty when it is outside
+    fn area(&self, x: f32, y: f32, w: f32, h: f32) -> (u32, u32, u32, u32) {
+        let (sw, sh) = self.luma.dimensions();
+        let clamp = |v: f32, max: u32| (v * self.ratio).round().clamp(0.0, max as f32) as u32;
+        let (x0, y0) = (clamp(x, sw), clamp(y, sh));
+        let (x1, y1) = (clamp(x + w, sw).max(x0), clamp(y + h, sh).max(y0));
+        (x0, y0, x1, y1)
+    }
+
+    /// Mean and standard deviation of luma inside of rect, in 0.0 ..= 1.0
+    pub fn luma_stats(&self, x: f32, y: f32, w: f32, h: f32) -> (f32, f32) {
+        let (x0, y0, x1, y1) = self.area(x, y, w, h);
+        let count = ((x1 - x0) * (y1 - y0)) as f32;
+        if count == 0.0 {
+            return (0.0, 0.0);
+        }
+
+        let (mut sum, mut sum_sq) = (0.0f32, 0.0f32);
+        for sy in y0..y1 {
+            for sx in x0..x1 {
+                let v = self.luma.get_pixel(sx, sy)[0] as f32 / 255.0;
+                sum += v;
+                sum_sq += v * v;
+            }
+        }
+
+        let mean = sum / count;
+        (mean, (sum_sq / count - mean * mean).max(0.0).sqrt())
+    }
+
+    /// How busy the rect is, sum of mean edge strength and luma deviation.
+    /// Zero is flat, rarely goes over 1.0
+    pub fn busyness(&self, x: f32, y: f32, w: f32, h: f32) -> f32 {
+        let (x0, y0, x1, y1) = self.area(x, y, w, h);
+        let count = ((x1 - x0) * (y1 - y0)) as f32;
+        if count == 0.0 {
+            return 0.0;
+        }
+
+        let mut sum = 0.0f32;
+        for sy in y0..y1 {
+            for sx in x0..x1 {
+                sum += self.gradient.get_pixel(sx, sy)[0] as f32;
+            }
+        }
+
+        // Sobel magnitude of step edge from black to white is 1020
+        let edge = sum / count / 1020.0;
+        let (_, deviation) = self.luma_stats(x, y, w, h);
+        edge + deviation
+    }
+}
+
+/// Relative luminance of sRGB value in 0.0 ..= 1.0
+fn luminance(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn color_luminance(color: image::Rgba<u8>) -> f32 {
+    let [r, g, b] = [color[0], color[1], color[2]].map(|x| luminance(x as f32 / 255.0));
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+fn contrast_ratio(a: f32, b: f32) -> f32 {
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Keep preferred color if it is readable on background, otherwise light or dark one.
+/// `background` is mean luma from `ContentSampler::luma_stats`
+pub(crate) fn contrast_color(preferred: image::Rgba<u8>, background: f32) -> image::Rgba<u8> {
+    let background = luminance(background);
+    if contrast_ratio(color_luminance(preferred), background) >= MIN_CONTRAST {
+        return preferred;
+    }
+
+    let candidate = if contrast_ratio(color_luminance(LIGHT), background)
+        >= contrast_ratio(color_luminance(DARK), background)
+    {
+        LIGHT
+    } else {
+        DARK
+    };
+    image::Rgba([candidate[0], candidate[1], candidate[2], preferred[3]])
+}
@@ -0,0 +1,489 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! User defined theme from Rhai script (`*.rhai`) in themes folder.
+//! Script runs in sandbox without file or network access, only drawing functions below are given.
+//!
+//! Constants
+//! - `exif` : map of template fields (`exif.focal`, `exif.lens_model`, ...), missing value is ""
+//! - `width`, `height` : size of photo in pixels
+//! - `vertical` : true when photo is portrait
+//! - `palette` : up to 8 dominant colors of photo as "#RRGGBB", most common first
+//!
+//! Functions, numbers are pixels of canvas and colors are "#rrggbb" or "#rrggbbaa".
+//! Coordinates and sizes must be within twice of long edge of canvas, font size within long edge
+//! - `pad(top, right, bottom, left, color)` : grow canvas around photo
+//! - `canvas_width()`, `canvas_height()`, `photo_x()`, `photo_y()`
+//! - `fill_rect(x, y, w, h, color)`, `rect(x, y, w, h, color)`
+//! - `line(x1, y1, x2, y2, color)`, `circle(x, y, radius, color)`
+//! - `set_font(name)` : "sans", "digits", "mono" or file name in fonts folder
+//! - `text(x, y, size, color, text)`, `text_width(size, text)`, `text_height(size)`
+//! - `text_effects(map)` : outline, shadow and plate of following `text()`, same keys as
+//!   `effects` of JSON theme, e.g. `text_effects(#{ outline: true, shadow_color: "#000000a0" })`
+//! - `image(path, x, y, height)` : image file relative to script
+//! - `luminance(x, y, w, h)` : average brightness of region, 0.0 ~ 1.0
+//!
+//! ```rhai
+//! let bar = height / 10;
+//! pad(0, 0, bar, 0, "#ffffff");
+//! let size = bar / 3;
+//! let caption = exif.camera_model;
+//! if exif.lens_model != "" && !exif.lens_model.contains("Kit") {
+//!     caption += "  " + exif.lens_model;
+//! }
+//! text(bar / 3, height + (bar - text_height(size)) / 2, size, "#202020", caption);
+//! ```
+
+use crate::fonts::{FontChain, FontChoice, OverlayFont};
+use crate::theme::{
+    Theme, custom::UNIQUE_NAME_PREFIX, template, text_dimensions, text_effects::TextEffects,
+};
+use rhai::{Dynamic, Engine, EvalAltResult, Scope};
+use rust_i18n::t;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// Canvas can be grown up to this times of long edge of photo on each side, sum of every `pad()`
+pub(crate) const MAX_PAD_RATIO: f32 = 4.0;
+/// Canvas never grows over this, 400MB of RGBA
+pub(crate) const MAX_CANVAS_PIXELS: u64 = 100_000_000;
+
+struct ScriptCanvas {
+    canvas: image::RgbaImage,
+    photo_x: u32,
+    photo_y: u32,
+    /// Size of photo before any `pad()`, limit of canvas is based on this
+    photo_size: (u32, u32),
+    font: FontChain,
+    effects: TextEffects,
+    /// Folder of script, `image()` cannot read outside of here
+    base: PathBuf,
+}
+
+fn num(value: Dynamic) -> ScriptResult<f32> {
+    match (value.as_float(), value.as_int()) {
+        (Ok(x), _) => Ok(x as f32),
+        (_, Ok(x)) => Ok(x as f32),
+        _ => Err(format!("expected number but {}", value.type_name()).into()),
+    }
+}
+
+fn color(text: &str) -> ScriptResult<image::Rgba<u8>> {
+    crate::theme::parse_hex_color(text).ok_or_else(|| {
+        format!("invalid color {text:?}, expected \"#rrggbb\" or \"#rrggbbaa\"").into()
+    })
+}
+
+fn rect(x: f32, y: f32, w: f32, h: f32) -> Option<imageproc::rect::Rect> {
+    let (w, h) = (w.round() as i64, h.round() as i64);
+    (w > 0 && h > 0).then(|| {
+        imageproc::rect::Rect::at(x.round() as i32, y.round() as i32).of_size(w as u32, h as u32)
+    })
+}
+
+impl ScriptCanvas {
+    /// Draw on canvas with alpha blending
+    fn blend(&mut self, draw: impl FnOnce(&mut imageproc::drawing::Blend<image::RgbaImage>)) {
+        let mut blend = imageproc::drawing::Blend(std::mem::take(&mut self.canvas));
+        draw(&mut blend);
+        self.canvas = blend.0;
+    }
+
+    fn pad(&mut self, sides: [f32; 4], background: image::Rgba<u8>) -> ScriptResult<()> {
+        let (w, h) = self.canvas.dimensions();
+        let (photo_w, photo_h) = self.photo_size;
+        let limit = photo_w.max(photo_h) as f32 * MAX_PAD_RATIO;
+        if sides.iter().any(|x| !(0.0..=limit).contains(x)) {
+            return Err(format!("pad must be between 0 and {limit}").into());
+        }
+
+        let [top, right, bottom, left] = sides.map(|x| x.round() as u32);
+        let (new_w, new_h) = (
+            left as u64 + w as u64 + right as u64,
+            top as u64 + h as u64 + bottom as u64,
+        );
+        // Repeated `pad()` cannot grow canvas over limit either
+        let max_w = photo_w as u64 + 2 * limit as u64;
+        let max_h = photo_h as u64 + 2 * limit as u64;
+        if new_w > max_w || new_h > max_h || new_w * new_h > MAX_CANVAS_PIXELS {
+            return Err(format!(
+                "canvas {new_w}x{new_h} is too large, up to {max_w}x{max_h} and {MAX_CANVAS_PIXELS} pixels"
+            )
+            .into());
+        }
+
+        let mut canvas =
+            image::RgbaImage::from_pixel(left + w + right, top + h + bottom, background);
+        image::imageops::overlay(&mut canvas, &self.canvas, left as i64, top as i64);
+
+        self.canvas = canvas;
+        self.photo_x += left;
+        self.photo_y += top;
+        Ok(())
+    }
+
+    fn image(&mut self, path: &str, x: f32, y: f32, height: f32) -> ScriptResult<()> {
+        let resolved = self
+            .base
+            .join(path)
+            .canonicalize()
+            .map_err(|e| format!("image {path:?} : {e}"))?;
+        if !resolved.starts_with(&self.base) {
+            return Err(format!("image {path:?} is outside of script folder").into());
+        }
+
+        let height = (height.round() as u32).clamp(1, self.canvas.height().max(1));
+        let stamp = crate::theme::load_image_with_height(&resolved, height)
+            .map_err(|e| format!("image {path:?} : {e}"))?;
+        image::imageops::overlay(&mut self.canvas, &stamp, x.round() as i64, y.round() as i64);
+        Ok(())
+    }
+
+    /// Drawing walks every point even off canvas, so coordinates and sizes
+    /// are limited to twice of long edge of canvas
+    fn bounded(&self, value: Dynamic) -> ScriptResult<f32> {
+        let x = num(value)?;
+        let limit = self.canvas.width().max(self.canvas.height()) as f32 * 2.0;
+        if !(-limit..=limit).contains(&x) {
+            return Err(
+                format!("{x} is out of canvas, must be between -{limit} and {limit}").into(),
+            );
+        }
+        Ok(x)
+    }
+
+    /// Font size up to long edge of canvas
+    fn font_scale(&self, size: Dynamic) -> ScriptResult<ab_glyph::PxScale> {
+        let size = num(size)?;
+        let limit = self.canvas.width().max(self.canvas.height()) as f32;
+        if !(1.0..=limit).contains(&size) {
+            return Err(format!("font size must be between 1 and {limit}").into());
+        }
+        Ok(ab_glyph::PxScale::from(size))
+    }
+
+    fn luminance(&self, x: f32, y: f32, w: f32, h: f32) -> f32 {
+        let (canvas_w, canvas_h) = self.canvas.dimensions();
+        let x0 = (x.round().max(0.0) as u32).min(canvas_w);
+        let y0 = (y.round().max(0.0) as u32).min(canvas_h);
+        let x1 = ((x + w).round().max(0.0) as u32).min(canvas_w);
+        let y1 = ((y + h).round().max(0.0) as u32).min(canvas_h);
+        if x1 <= x0 || y1 <= y0 {
+            return 0.0;
+        }
+
+        // Sampling is enough for average
+        let step = (((x1 - x0) * (y1 - y0)) as f32 / 65536.0).sqrt().max(1.0) as usize;
+        let (mut sum, mut count) = (0.0, 0.0);
+        for py in (y0..y1).step_by(step) {
+            for px in (x0..x1).step_by(step) {
+                let [r, g, b, _] = self.canvas.get_pixel(px, py).0;
+                sum += 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32;
+                count += 1.0;
+            }
+        }
+        sum / count / 255.0
+    }
+}
+
+/// Engine with resource limits, without any drawing function
+fn sandbox_engine() -> Engine {
+    let mut engine = Engine::new();
+    // No `import` of other script files
+    engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+    engine.set_max_operations(5_000_000);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(64 * 1024);
+    engine.set_max_array_size(16 * 1024);
+    engine.set_max_map_size(1024);
+    engine.disable_symbol("eval");
+    engine.on_print(|x| log::info!("[script] {x}"));
+    engine.on_debug(|x, _, pos| log::debug!("[script] {pos:?} {x}"));
+    engine
+}
+
+fn register_api(engine: &mut Engine, state: &Rc<RefCell<ScriptCanvas>>) {
+    let s = state.clone();
+    engine.register_fn(
+        "pad",
+        move |top: Dynamic, right: Dynamic, bottom: Dynamic, left: Dynamic, background: &str| {
+            let sides = [num(top)?, num(right)?, num(bottom)?, num(left)?];
+            s.borrow_mut().pad(sides, color(background)?)
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn("canvas_width", move || s.borrow().canvas.width() as i64);
+    let s = state.clone();
+    engine.register_fn("canvas_height", move || s.borrow().canvas.height() as i64);
+    let s = state.clone();
+    engine.register_fn("photo_x", move || s.borrow().photo_x as i64);
+    let s = state.clone();
+    engine.register_fn("photo_y", move || s.borrow().photo_y as i64);
+
+    let s = state.clone();
+    engine.register_fn(
+        "fill_rect",
+        move |x: Dynamic, y: Dynamic, w: Dynamic, h: Dynamic, c: &str| -> ScriptResult<()> {
+            let mut st = s.borrow_mut();
+            let (x, y) = (st.bounded(x)?, st.bounded(y)?);
+            let (c, r) = (color(c)?, rect(x, y, st.bounded(w)?, st.bounded(h)?));
+            if let Some(r) = r {
+                st.blend(|canvas| imageproc::drawing::draw_filled_rect_mut(canvas, r, c));
+            }
+            Ok(())
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn(
+        "rect",
+        move |x: Dynamic, y: Dynamic, w: Dynamic, h: Dynamic, c: &str| -> ScriptResult<()> {
+            let mut st = s.borrow_mut();
+            let (x, y) = (st.bounded(x)?, st.bounded(y)?);
+            let (c, r) = (color(c)?, rect(x, y, st.bounded(w)?, st.bounded(h)?));
+            if let Some(r) = r {
+                st.blend(|canvas| imageproc::drawing::draw_hollow_rect_mut(canvas, r, c));
+            }
+            Ok(())
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn(
+        "line",
+        move |x1: Dynamic, y1: Dynamic, x2: Dynamic, y2: Dynamic, c: &str| -> ScriptResult<()> {
+            let mut st = s.borrow_mut();
+            let start = (st.bounded(x1)?, st.bounded(y1)?);
+            let (end, c) = ((st.bounded(x2)?, st.bounded(y2)?), color(c)?);
+            st.blend(|canvas| imageproc::drawing::draw_line_segment_mut(canvas, start, end, c));
+            Ok(())
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn(
+        "circle",
+        move |x: Dynamic, y: Dynamic, radius: Dynamic, c: &str| -> ScriptResult<()> {
+            let mut st = s.borrow_mut();
+            let center = (st.bounded(x)?.round() as i32, st.bounded(y)?.round() as i32);
+            let (radius, c) = (st.bounded(radius)?.round() as i32, color(c)?);
+            if radius > 0 {
+                st.blend(|canvas| {
+                    imageproc::drawing::draw_filled_circle_mut(canvas, center, radius, c)
+                });
+            }
+            Ok(())
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn("set_font", move |name: &str| -> ScriptResult<()> {
+        s.borrow_mut().font = FontChoice::from(name.to_owned())
+            .try_chain()
+            .ok_or_else(|| {
+                format!("unknown font {name:?}, expected \"sans\", \"digits\", \"mono\" or file name in fonts folder")
+            })?;
+        Ok(())
+    });
+
+    let s = state.clone();
+    engine.register_fn(
+        "text",
+        move |x: Dynamic, y: Dynamic, size: Dynamic, c: &str, text: &str| -> ScriptResult<()> {
+            let st = &mut *s.borrow_mut();
+            let (x, y, c) = (st.bounded(x)?, st.bounded(y)?, color(c)?);
+            let scale = st.font_scale(size)?;
+            let (w, h) = text_dimensions(scale, &st.font, text);
+            if w as u64 * h as u64 > MAX_CANVAS_PIXELS {
+                return Err(
+                    format!("text {w}x{h} is too large, up to {MAX_CANVAS_PIXELS} pixels").into(),
+                );
+            }
+            st.effects.draw_plate(&mut st.canvas, x, y, w, h, scale.y);
+            st.effects
+                .draw_text(&mut st.canvas, &st.font, scale, x, y, c, text);
+            Ok(())
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn("text_effects", move |map: rhai::Map| -> ScriptResult<()> {
+        let effects: TextEffects = serde_json::from_str(&rhai::format_map_as_json(&map))
+            .map_err(|e| format!("text_effects : {e}"))?;
+        // Same ranges as sliders, outline and blur cost grows with these
+        let percents = [
+            effects.outline_width,
+            effects.shadow_blur,
+            effects.shadow_offset,
+            effects.plate_padding,
+            effects.plate_radius,
+        ];
+        if percents.iter().any(|x| !(0.0..=100.0).contains(x)) {
+            return Err(
+                "text_effects : widths must be between 0 and 100 percent of font size".into(),
+            );
+        }
+        s.borrow_mut().effects = effects;
+        Ok(())
+    });
+
+    let s = state.clone();
+    engine.register_fn(
+        "text_width",
+        move |size: Dynamic, text: &str| -> ScriptResult<f64> {
+            let st = s.borrow();
+            Ok(text_dimensions(st.font_scale(size)?, &st.font, text).0 as f64)
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn("text_height", move |size: Dynamic| -> ScriptResult<f64> {
+        let st = s.borrow();
+        Ok(text_dimensions(st.font_scale(size)?, &st.font, "").1 as f64)
+    });
+
+    let s = state.clone();
+    engine.register_fn(
+        "image",
+        move |path: &str, x: Dynamic, y: Dynamic, height: Dynamic| -> ScriptResult<()> {
+            let (x, y, height) = (num(x)?, num(y)?, num(height)?);
+            s.borrow_mut().image(path, x, y, height)
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn(
+        "luminance",
+        move |x: Dynamic, y: Dynamic, w: Dynamic, h: Dynamic| -> ScriptResult<f64> {
+            let (x, y, w, h) = (num(x)?, num(y)?, num(w)?, num(h)?);
+            Ok(s.borrow().luminance(x, y, w, h) as f64)
+        },
+    );
+}
+
+pub struct ScriptTheme {
+    /// `Theme::unique_name` requires static lifetime
+    unique_name: &'static str,
+    label: String,
+    source: PathBuf,
+    /// Compiled on every apply, `rhai::AST` is not `Send`
+    script: String,
+}
+
+impl ScriptTheme {
+    /// Compile script, error message contains path and reason
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let error = |reason: String| format!("{}: {reason}", path.display());
+
+        let text = std::fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+        sandbox_engine()
+            .compile(&text)
+            .map_err(|e| error(e.to_string()))?;
+
+        let name = path
+            .file_stem()
+            .map(|x| x.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Ok(Self {
+            unique_name: crate::theme::intern(format!("{UNIQUE_NAME_PREFIX}{name}")),
+            label: name,
+            source: path.to_path_buf(),
+            script: text,
+        })
+    }
+}
+
+impl Theme for ScriptTheme {
+    fn unique_name(&self) -> &'static str {
+        self.unique_name
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        std::borrow::Cow::Owned(self.label.clone())
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let photo = pi.developed(export_config)?.to_rgba8();
+        let (img_w, img_h) = photo.dimensions();
+        let palette = crate::theme::palette::dominant_colors(&photo, 8)
+            .into_iter()
+            .map(|x| Dynamic::from(crate::theme::palette::hex(x)))
+            .collect::<rhai::Array>();
+
+        let base = self
+            .source
+            .parent()
+            .and_then(|x| x.canonicalize().ok())
+            .unwrap_or_default();
+        let state = Rc::new(RefCell::new(ScriptCanvas {
+            photo_size: photo.dimensions(),
+            canvas: photo,
+            photo_x: 0,
+            photo_y: 0,
+            font: FontChoice::Bundled(OverlayFont::Sans).chain(),
+            effects: TextEffects::default(),
+            base,
+        }));
+
+        let mut engine = sandbox_engine();
+        register_api(&mut engine, &state);
+
+        let exif = template::FIELDS
+            .iter()
+            .map(|field| {
+                let value = template::field_value(&pi.view_exif, field).unwrap_or_default();
+                ((*field).into(), Dynamic::from(value))
+            })
+            .collect::<rhai::Map>();
+
+        let mut scope = Scope::new();
+        scope.push_constant("exif", exif);
+        scope.push_constant("width", img_w as i64);
+        scope.push_constant("height", img_h as i64);
+        scope.push_constant("vertical", img_h > img_w);
+        scope.push_constant("palette", palette);
+
+        engine
+            .run_with_scope(&mut scope, &self.script)
+            .map_err(|e| {
+                image::ImageError::Parameter(image::error::ParameterError::from_kind(
+                    image::error::ParameterErrorKind::Generic(format!(
+                        "{}: {e}",
+                        self.source.display()
+                    )),
+                ))
+            })?;
+
+        // Engine holds clones of state in registered functions
+        drop(engine);
+        let canvas = std::mem::take(&mut state.borrow_mut().canvas);
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.label(t!(
+                "theme.custom_config.source",
+                path = self.source.display().to_string()
+            ));
+            ui.label(t!("theme.custom_config.reload_hint_script"));
+        });
+    }
+}
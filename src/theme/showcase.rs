@@ -0,0 +1,203 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Photo with rounded corners and soft drop shadow on flat or gradient background,
+//! for presentation slides. Transparent background is kept on PNG output,
+//! JPEG and WebP are flattened over white by `OutputFormat::save_image`.
+
+use crate::theme::{Theme, blur_canvas::CanvasAspect, effects};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+#[derive(EnumIter, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum BackgroundFill {
+    Flat,
+    Gradient,
+}
+
+impl BackgroundFill {
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            Self::Flat => t!("theme.showcase_config.flat"),
+            Self::Gradient => t!("theme.showcase_config.gradient"),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Showcase {
+    aspect: CanvasAspect,
+    /// Minimum margin around photo in percent of its long edge
+    margin: f32,
+    /// Corner radius in percent of photo short edge
+    corner_radius: f32,
+    shadow: bool,
+    /// Shadow blur in percent of photo short edge
+    shadow_blur: f32,
+    /// Shadow offset to right and bottom in percent of photo short edge
+    shadow_offset: [f32; 2],
+    shadow_color: egui::Color32,
+    fill: BackgroundFill,
+    background: egui::Color32,
+    /// End color of gradient
+    background_end: egui::Color32,
+    /// Gradient direction in degree, 0 is top to bottom
+    gradient_angle: f32,
+}
+
+impl core::default::Default for Showcase {
+    fn default() -> Self {
+        Self {
+            aspect: CanvasAspect::Wide,
+            margin: 8.0,
+            corner_radius: 3.0,
+            shadow: true,
+            shadow_blur: 3.0,
+            shadow_offset: [0.0, 1.5],
+            shadow_color: egui::Color32::from_black_alpha(110),
+            fill: BackgroundFill::Gradient,
+            background: egui::Color32::from_rgb(0xEE, 0xF1, 0xF5),
+            background_end: egui::Color32::from_rgb(0xC9, 0xD3, 0xDF),
+            gradient_angle: 0.0,
+        }
+    }
+}
+
+impl Theme for Showcase {
+    fn unique_name(&self) -> &'static str {
+        "showcase"
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        t!("theme.showcase")
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let mut photo = pi.developed(export_config)?.to_rgba8();
+        let (img_w, img_h) = photo.dimensions();
+        let (canvas_w, canvas_h) = self.aspect.canvas_size(img_w, img_h, self.margin);
+        let (photo_x, photo_y) = ((canvas_w - img_w) / 2, (canvas_h - img_h) / 2);
+
+        let background = crate::theme::color32_to_rgba(self.background);
+        let mut canvas = match self.fill {
+            BackgroundFill::Flat => image::RgbaImage::from_pixel(canvas_w, canvas_h, background),
+            BackgroundFill::Gradient => effects::linear_gradient(
+                canvas_w,
+                canvas_h,
+                background,
+                crate::theme::color32_to_rgba(self.background_end),
+                self.gradient_angle,
+            ),
+        };
+
+        let short = img_w.min(img_h) as f32;
+        let mask = effects::rounded_rect_mask(img_w, img_h, short * self.corner_radius / 100.0);
+
+        if self.shadow {
+            let (shadow, padding) = effects::soft_shadow(&mask, short * self.shadow_blur / 100.0);
+            let [dx, dy] = self
+                .shadow_offset
+                .map(|x| (short * x / 100.0).round() as i64);
+            crate::theme::blend_mask_mut(
+                &mut canvas,
+                &shadow,
+                photo_x as i64 - padding as i64 + dx,
+                photo_y as i64 - padding as i64 + dy,
+                crate::theme::color32_to_rgba(self.shadow_color),
+            );
+        }
+
+        effects::apply_mask_alpha(&mut photo, &mask);
+        image::imageops::overlay(&mut canvas, &photo, photo_x as i64, photo_y as i64);
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.blur_canvas_config.aspect"));
+                self.aspect.update_ui(ui, "showcase_aspect");
+            });
+            ui.add(
+                egui::Slider::new(&mut self.margin, 0.0..=30.0)
+                    .suffix("%")
+                    .text(t!("theme.blur_canvas_config.margin")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.corner_radius, 0.0..=20.0)
+                    .suffix("%")
+                    .text(t!("theme.blur_canvas_config.corner_radius")),
+            );
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.shadow, t!("theme.blur_canvas_config.shadow"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.shadow_color,
+                    egui::color_picker::Alpha::OnlyBlend,
+                );
+            });
+            ui.add_enabled_ui(self.shadow, |ui| {
+                ui.add(
+                    egui::Slider::new(&mut self.shadow_blur, 0.0..=10.0)
+                        .suffix("%")
+                        .text(t!("theme.blur_canvas_config.shadow_blur")),
+                );
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme.showcase_config.shadow_offset"));
+                    for value in self.shadow_offset.iter_mut() {
+                        ui.add(
+                            egui::DragValue::new(value)
+                                .range(-10.0..=10.0)
+                                .speed(0.1)
+                                .suffix("%"),
+                        );
+                    }
+                });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.background_color"));
+                egui::ComboBox::from_id_salt("showcase_fill")
+                    .selected_text(self.fill.label())
+                    .show_ui(ui, |ui| {
+                        for fill in BackgroundFill::iter() {
+                            ui.selectable_value(&mut self.fill, fill, fill.label());
+                        }
+                    });
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.background,
+                    egui::color_picker::Alpha::OnlyBlend,
+                );
+                if self.fill == BackgroundFill::Gradient {
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.background_end,
+                        egui::color_picker::Alpha::OnlyBlend,
+                    );
+                }
+            });
+            if self.fill == BackgroundFill::Gradient {
+                ui.add(
+                    egui::Slider::new(&mut self.gradient_angle, 0.0..=360.0)
+                        .suffix("°")
+                        .text(t!("theme.showcase_config.gradient_angle")),
+                );
+            }
+            ui.label(t!("theme.showcase_config.transparency_hint"));
+        });
+    }
+}
@@ -0,0 +1,325 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Text template bound to `SimplifiedExif` fields.
+//! - `{field}` is replaced with value of field, missing value is replaced with empty string
+//! - `{field:spec}` formats value, see `FORMATS`
+//! - `[...]` is optional group, dropped when any field inside is missing
+//! - `{{`, `}}`, `[[` and `]]` are literal characters
+
+use crate::exif_impl::SimplifiedExif;
+
+/// Every field name usable in template
+pub const FIELDS: &[&str] = &[
+    "camera_mnf",
+    "camera_model",
+    "lens_model",
+    "focal",
+    "fnumber",
+    "exposure",
+    "iso",
+    "datetime",
+    "date",
+    "time",
+    "artist",
+    "copyright",
+    "settings",
+];
+
+pub(crate) fn field_value(exif: &SimplifiedExif, field: &str) -> Option<String> {
+    match field {
+        "camera_mnf" => Some(exif.camera_mnf.clone()),
+        "camera_model" => Some(exif.camera_model.clone()),
+        "lens_model" => Some(exif.lens_model.clone()),
+        "focal" => exif.get_focal(),
+        "fnumber" => exif.get_fnumber(),
+        "exposure" => exif.get_exposure(),
+        "iso" => exif.get_iso(),
+        "datetime" => Some(exif.datetime.clone()),
+        "date" => exif
+            .get_datetime()
+            .map(|dt| format!("{:04}-{:02}-{:02}", dt.year, dt.month, dt.day)),
+        "time" => exif
+            .get_datetime()
+            .map(|dt| format!("{:02}:{:02}:{:02}", dt.hour, dt.minute, dt.second)),
+        "artist" => Some(exif.artist.clone()),
+        "copyright" => Some(exif.copyright.clone()),
+        "settings" => Some(exif.settings_summary()),
+        _ => None,
+    }
+}
+
+/// Every format spec usable with `{field:spec}`, `.N` is also usable for N decimal places
+pub const FORMATS: &[&str] = &["fraction", "decimal", "upper", "lower"];
+
+/// Parse "1/250", "0.004" or "2.5" to number
+fn parse_number(value: &str) -> Option<f64> {
+    match value.split_once('/') {
+        Some((num, den)) => {
+            let (num, den) = (
+                num.trim().parse::<f64>().ok()?,
+                den.trim().parse::<f64>().ok()?,
+            );
+            (den != 0.0).then_some(num / den)
+        }
+        None => value.trim().parse::<f64>().ok(),
+    }
+}
+
+/// Shortest decimal up to `places`, "0.0040" -> "0.004"
+fn trim_decimal(value: f64, places: usize) -> String {
+    let text = format!("{value:.places$}");
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_owned()
+    } else {
+        text
+    }
+}
+
+fn check_format(spec: &str) -> Result<(), String> {
+    if FORMATS.contains(&spec)
+        || spec
+            .strip_prefix('.')
+            .is_some_and(|x| x.parse::<u8>().is_ok_and(|x| x <= 9))
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "unknown format {spec:?}, available formats are {} and .N",
+            FORMATS.join(", ")
+        ))
+    }
+}
+
+/// Apply format spec, value that is not a number is kept on numeric format
+fn format_value(value: String, spec: &str) -> String {
+    match spec {
+        "upper" => value.to_uppercase(),
+        "lower" => value.to_lowercase(),
+        "fraction" => match parse_number(&value) {
+            // 1/250 for fast shutter, 2.5 for long exposure
+            Some(x) if x > 0.0 && x < 1.0 => format!("1/{}", (1.0 / x).round()),
+            Some(x) => trim_decimal(x, 1),
+            None => value,
+        },
+        "decimal" => match parse_number(&value) {
+            Some(x) => trim_decimal(x, 4),
+            None => value,
+        },
+        places => match (places.strip_prefix('.'), parse_number(&value)) {
+            (Some(places), Some(x)) => format!("{x:.*}", places.parse::<usize>().unwrap_or(0)),
+            _ => value,
+        },
+    }
+}
+
+enum Token<'a> {
+    Literal(&'a str),
+    Field(&'a str, Option<&'a str>),
+    GroupStart,
+    GroupEnd,
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    let mut depth = 0usize;
+
+    while let Some(pos) = rest.find(['{', '}', '[', ']']) {
+        if pos > 0 {
+            tokens.push(Token::Literal(&rest[..pos]));
+        }
+        let tail = &rest[pos..];
+
+        if let Some(escaped) = ["{{", "}}", "[[", "]]"]
+            .into_iter()
+            .find(|x| tail.starts_with(x))
+        {
+            tokens.push(Token::Literal(&escaped[..1]));
+            rest = &tail[2..];
+        } else if let Some(tail) = tail.strip_prefix('[') {
+            tokens.push(Token::GroupStart);
+            depth += 1;
+            rest = tail;
+        } else if let Some(tail) = tail.strip_prefix(']') {
+            depth = depth
+                .checked_sub(1)
+                .ok_or_else(|| format!("unmatched ']' in {template:?}"))?;
+            tokens.push(Token::GroupEnd);
+            rest = tail;
+        } else if tail.starts_with('}') {
+            return Err(format!("unmatched '}}' in {template:?}"));
+        } else {
+            let end = tail
+                .find('}')
+                .ok_or_else(|| format!("unclosed '{{' in {template:?}"))?;
+            let (field, spec) = match tail[1..end].split_once(':') {
+                Some((field, spec)) => (field.trim(), Some(spec.trim())),
+                None => (tail[1..end].trim(), None),
+            };
+            tokens.push(Token::Field(field, spec));
+            rest = &tail[end + 1..];
+        }
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Literal(rest));
+    }
+
+    if depth > 0 {
+        return Err(format!("unclosed '[' in {template:?}"));
+    }
+
+    Ok(tokens)
+}
+
+/// Check syntax, field names and formats of template
+pub fn validate(template: &str) -> Result<(), String> {
+    for token in tokenize(template)? {
+        if let Token::Field(field, spec) = token {
+            if !FIELDS.contains(&field) {
+                return Err(format!(
+                    "unknown field {{{field}}}, available fields are {}",
+                    FIELDS.join(", ")
+                ));
+            }
+            if let Some(spec) = spec {
+                check_format(spec)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replace fields with EXIF values, invalid template is rendered as it is
+pub fn render(template: &str, exif: &SimplifiedExif) -> String {
+    let Ok(tokens) = tokenize(template) else {
+        return template.to_owned();
+    };
+
+    // (text, every field in group has value)
+    let mut groups = vec![(String::new(), true)];
+
+    for token in tokens {
+        match token {
+            Token::Literal(x) => groups.last_mut().unwrap().0.push_str(x),
+            Token::Field(field, spec) => {
+                let value = field_value(exif, field).filter(|x| !x.is_empty());
+                let group = groups.last_mut().unwrap();
+                match value {
+                    Some(value) => group.0.push_str(&match spec {
+                        Some(spec) => format_value(value, spec),
+                        None => value,
+                    }),
+                    None => group.1 = false,
+                }
+            }
+            Token::GroupStart => groups.push((String::new(), true)),
+            Token::GroupEnd => {
+                let (text, present) = groups.pop().unwrap();
+                if present {
+                    groups.last_mut().unwrap().0.push_str(&text);
+                }
+            }
+        }
+    }
+
+    groups.pop().map(|(text, _)| text).unwrap_or_default()
+}
+
+/// Same as `SimplifiedExif::settings_summary`, "35mm  f/2.8  1/250s  ISO100"
+pub const DEFAULT_SETTINGS: &str = "[{focal}mm  ][f/{fnumber}  ][{exposure}s  ][ISO{iso}]";
+
+/// Single line editor of template with syntax check
+pub fn update_ui(ui: &mut egui::Ui, template: &mut String) -> egui::Response {
+    let response = ui
+        .add(egui::TextEdit::singleline(template).desired_width(240.0))
+        .on_hover_text(rust_i18n::t!(
+            "theme.template_description",
+            fields = FIELDS.join(", "),
+            formats = FORMATS.join(", ")
+        ));
+
+    if let Err(e) = validate(template) {
+        ui.colored_label(ui.visuals().error_fg_color, e);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exif() -> SimplifiedExif {
+        SimplifiedExif {
+            camera_model: "X100V".to_owned(),
+            focal: "35".to_owned(),
+            fnumber: "2.8".to_owned(),
+            exposure: "0.004".to_owned(),
+            iso_speed: Some(100),
+            datetime: "2025-10-17 12:34:56".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn render_fields_and_formats() {
+        let exif = exif();
+        assert_eq!(render("{camera_model} {iso}", &exif), "X100V 100");
+        assert_eq!(render("{ camera_model : lower }", &exif), "x100v");
+        assert_eq!(render("{exposure:fraction}s", &exif), "1/250s");
+        assert_eq!(render("{exposure:decimal}", &exif), "0.004");
+        assert_eq!(render("f/{fnumber:.2}", &exif), "f/2.80");
+        assert_eq!(render("{date} {time}", &exif), "2025-10-17 12:34:56");
+        // Value that is not a number is kept
+        assert_eq!(render("{camera_model:.1}", &exif), "X100V");
+        // Missing field outside of group is empty
+        assert_eq!(render("{lens_model}|{artist:upper}", &exif), "|");
+    }
+
+    #[test]
+    fn render_optional_groups() {
+        let mut exif = exif();
+        assert_eq!(render(DEFAULT_SETTINGS, &exif), exif.settings_summary());
+        assert_eq!(render("{camera_model}[ / {lens_model}]", &exif), "X100V");
+        assert_eq!(
+            render("[{camera_model}[ with {lens_model}]!]", &exif),
+            "X100V!"
+        );
+
+        exif.fnumber = "0".to_owned();
+        exif.lens_model = "XF23mm".to_owned();
+        assert_eq!(render(DEFAULT_SETTINGS, &exif), "35mm  0.004s  ISO100");
+        assert_eq!(render(DEFAULT_SETTINGS, &exif), exif.settings_summary());
+        assert_eq!(
+            render("[{camera_model}[ with {lens_model}]!]", &exif),
+            "X100V with XF23mm!"
+        );
+        assert_eq!(render("[{lens_model} f/{fnumber}]", &exif), "");
+    }
+
+    #[test]
+    fn render_escapes_and_invalid() {
+        let exif = exif();
+        assert_eq!(render("{{[[{iso}]]}}", &exif), "{[100]}");
+        // Invalid template is rendered as it is
+        assert_eq!(render("[{iso}", &exif), "[{iso}");
+    }
+
+    #[test]
+    fn validate_template() {
+        assert!(validate(DEFAULT_SETTINGS).is_ok());
+        assert!(validate("{exposure:fraction} {focal:.0} {{literal}}").is_ok());
+        assert!(validate("{unknown}").is_err());
+        assert!(validate("{focal:bogus}").is_err());
+        assert!(validate("{focal:.10}").is_err());
+        assert!(validate("{focal").is_err());
+        assert!(validate("focal}").is_err());
+        assert!(validate("[{focal}").is_err());
+        assert!(validate("{focal}]").is_err());
+    }
+}
@@ -0,0 +1,267 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Outline, soft drop shadow and rounded backing plate to keep text readable on busy photos.
+//! Every size is percent of font size, so the look is same on any `ScaleConfig`.
+
+use crate::fonts::FontChain;
+use crate::theme::effects;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+
+/// Colors are "#rrggbb" or "#rrggbbaa" in JSON
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TextEffects {
+    pub outline: bool,
+    /// Outline width in percent of font size
+    pub outline_width: f32,
+    #[serde(with = "crate::theme::hex_color32")]
+    pub outline_color: egui::Color32,
+
+    pub shadow: bool,
+    /// Shadow blur in percent of font size
+    pub shadow_blur: f32,
+    /// Shadow offset to lower right in percent of font size
+    pub shadow_offset: f32,
+    #[serde(with = "crate::theme::hex_color32")]
+    pub shadow_color: egui::Color32,
+
+    /// Plate behind whole text block
+    pub plate: bool,
+    /// Space between text and edge of plate in percent of font size
+    pub plate_padding: f32,
+    /// Corner radius in percent of font size
+    pub plate_radius: f32,
+    #[serde(with = "crate::theme::hex_color32")]
+    pub plate_color: egui::Color32,
+}
+
+impl core::default::Default for TextEffects {
+    fn default() -> Self {
+        Self {
+            outline: false,
+            outline_width: 6.0,
+            outline_color: egui::Color32::BLACK,
+            shadow: false,
+            shadow_blur: 8.0,
+            shadow_offset: 4.0,
+            shadow_color: egui::Color32::from_black_alpha(160),
+            plate: false,
+            plate_padding: 30.0,
+            plate_radius: 25.0,
+            plate_color: egui::Color32::from_black_alpha(110),
+        }
+    }
+}
+
+impl TextEffects {
+    /// Draw rounded plate on block of text, `size` is font size in pixels
+    pub fn draw_plate(
+        &self,
+        canvas: &mut image::RgbaImage,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        size: f32,
+    ) {
+        if !self.plate || w <= 0.0 || h <= 0.0 {
+            return;
+        }
+
+        let padding = size * self.plate_padding / 100.0;
+        let (plate_w, plate_h) = (
+            (w + padding * 2.0).round() as u32,
+            (h + padding * 2.0).round() as u32,
+        );
+        let mask = effects::rounded_rect_mask(plate_w, plate_h, size * self.plate_radius / 100.0);
+
+        crate::theme::blend_mask_mut(
+            canvas,
+            &mask,
+            (x - padding).round() as i64,
+            (y - padding).round() as i64,
+            crate::theme::color32_to_rgba(self.plate_color),
+        );
+    }
+
+    /// Outline width and shadow blur sigma in pixels for font size `size`
+    fn widths(&self, size: f32) -> (u8, f32) {
+        let outline = if self.outline {
+            (size * self.outline_width / 100.0)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        } else {
+            0
+        };
+        let sigma = if self.shadow {
+            size * self.shadow_blur / 100.0
+        } else {
+            0.0
+        };
+        (outline, sigma)
+    }
+
+    /// Padding of text mask required by outline and shadow
+    pub fn padding(&self, size: f32) -> u32 {
+        let (outline, sigma) = self.widths(size);
+        outline as u32 + (sigma * 3.0).ceil() as u32
+    }
+
+    /// Space around text covered by outline, shadow and plate, for text drawn on its own image
+    pub fn extent(&self, size: f32) -> u32 {
+        let shadow_offset = if self.shadow {
+            (size * self.shadow_offset / 100.0).round() as u32
+        } else {
+            0
+        };
+        let plate = if self.plate {
+            (size * self.plate_padding / 100.0).ceil() as u32
+        } else {
+            0
+        };
+        (self.padding(size) + shadow_offset).max(plate)
+    }
+
+    /// Draw shadow and outline of text mask at (x, y) of mask, but not the text itself.
+    /// Mask should have `padding` around text.
+    pub fn draw_backdrop(
+        &self,
+        canvas: &mut image::RgbaImage,
+        mask: &image::GrayImage,
+        x: i64,
+        y: i64,
+        size: f32,
+    ) {
+        let (outline, sigma) = self.widths(size);
+
+        // Outline also casts shadow
+        let outlined = (outline > 0).then(|| {
+            imageproc::morphology::grayscale_dilate(
+                mask,
+                &imageproc::morphology::Mask::disk(outline),
+            )
+        });
+        let body = outlined.as_ref().unwrap_or(mask);
+
+        if self.shadow {
+            let shadow = if sigma > 0.0 {
+                image::imageops::fast_blur(body, sigma)
+            } else {
+                body.clone()
+            };
+            let offset = (size * self.shadow_offset / 100.0).round() as i64;
+            crate::theme::blend_mask_mut(
+                canvas,
+                &shadow,
+                x + offset,
+                y + offset,
+                crate::theme::color32_to_rgba(self.shadow_color),
+            );
+        }
+
+        if let Some(outlined) = &outlined {
+            crate::theme::blend_mask_mut(
+                canvas,
+                outlined,
+                x,
+                y,
+                crate::theme::color32_to_rgba(self.outline_color),
+            );
+        }
+    }
+
+    /// Draw single line text with shadow and outline, `y` is top of line
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text(
+        &self,
+        canvas: &mut image::RgbaImage,
+        font: &FontChain,
+        scale: ab_glyph::PxScale,
+        x: f32,
+        y: f32,
+        color: image::Rgba<u8>,
+        text: &str,
+    ) {
+        if text.is_empty() {
+            return;
+        }
+
+        let padding = self.padding(scale.y);
+        let mask = crate::theme::text_mask(scale, font, text, padding);
+        let (mx, my) = (
+            x.round() as i64 - padding as i64,
+            y.round() as i64 - padding as i64,
+        );
+
+        self.draw_backdrop(canvas, &mask, mx, my, scale.y);
+        crate::theme::blend_mask_mut(canvas, &mask, mx, my, color);
+    }
+
+    pub fn update_ui(&mut self, ui: &mut egui::Ui, id_salt: impl std::hash::Hash) {
+        egui::CollapsingHeader::new(t!("theme.text_effects.label"))
+            .id_salt(id_salt)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.outline, t!("theme.text_effects.outline"));
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.outline_color,
+                        egui::color_picker::Alpha::OnlyBlend,
+                    );
+                });
+                ui.add_enabled(
+                    self.outline,
+                    egui::Slider::new(&mut self.outline_width, 1.0..=30.0)
+                        .suffix("%")
+                        .text(t!("theme.text_effects.outline_width")),
+                );
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.shadow, t!("theme.text_effects.shadow"));
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.shadow_color,
+                        egui::color_picker::Alpha::OnlyBlend,
+                    );
+                });
+                ui.add_enabled(
+                    self.shadow,
+                    egui::Slider::new(&mut self.shadow_blur, 0.0..=50.0)
+                        .suffix("%")
+                        .text(t!("theme.text_effects.shadow_blur")),
+                );
+                ui.add_enabled(
+                    self.shadow,
+                    egui::Slider::new(&mut self.shadow_offset, 0.0..=50.0)
+                        .suffix("%")
+                        .text(t!("theme.text_effects.shadow_offset")),
+                );
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.plate, t!("theme.text_effects.plate"));
+                    egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut self.plate_color,
+                        egui::color_picker::Alpha::OnlyBlend,
+                    );
+                });
+                ui.add_enabled(
+                    self.plate,
+                    egui::Slider::new(&mut self.plate_padding, 0.0..=100.0)
+                        .suffix("%")
+                        .text(t!("theme.text_effects.plate_padding")),
+                );
+                ui.add_enabled(
+                    self.plate,
+                    egui::Slider::new(&mut self.plate_radius, 0.0..=100.0)
+                        .suffix("%")
+                        .text(t!("theme.text_effects.plate_radius")),
+                );
+            });
+    }
+}
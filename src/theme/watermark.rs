@@ -0,0 +1,284 @@
+/*
+ * SPDX-FileCopyrightText: © 2025 Jinwoo Park (pmnxis@gmail.com)
+ *
+ * SPDX-License-Identifier: LicenseRef-Non-AI-MIT
+ */
+
+//! Image (PNG/SVG) and text watermark.
+//! Every size is relative to short edge of output, so the look is same on any `ScaleConfig`.
+
+use crate::fonts::{FontChoice, OverlayFont};
+use crate::theme::{Theme, anchor::Anchor, effects, text_dimensions, text_effects::TextEffects};
+use rust_i18n::t;
+use std::path::PathBuf;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Watermark {
+    /// PNG/SVG or other image file, `None` for text only
+    logo_path: Option<PathBuf>,
+    show_text: bool,
+    /// Empty to use EXIF Copyright or Artist
+    text: String,
+    anchor: Anchor,
+    /// Margin in percent of short edge
+    margin: f32,
+    /// Watermark height in percent of short edge
+    scale: f32,
+    /// Opacity in percent
+    opacity: f32,
+    /// Clockwise degree
+    rotation: f32,
+    /// Repeat watermark over whole image, anchor and margin are ignored
+    tiled: bool,
+    font: FontChoice,
+    font_color: egui::Color32,
+    /// Outline, shadow and plate of text, faded together with text by opacity
+    effects: TextEffects,
+}
+
+impl core::default::Default for Watermark {
+    fn default() -> Self {
+        Self {
+            logo_path: None,
+            show_text: true,
+            text: String::new(),
+            anchor: Anchor::BottomRight,
+            margin: 3.0,
+            scale: 4.0,
+            opacity: 60.0,
+            rotation: 0.0,
+            tiled: false,
+            font: FontChoice::Bundled(OverlayFont::Sans),
+            font_color: egui::Color32::WHITE,
+            effects: TextEffects::default(),
+        }
+    }
+}
+
+impl Watermark {
+    fn watermark_text(&self, exif: &crate::exif_impl::SimplifiedExif) -> Option<String> {
+        if !self.text.trim().is_empty() {
+            Some(self.text.clone())
+        } else if !exif.copyright.is_empty() {
+            Some(exif.copyright.clone())
+        } else if !exif.artist.is_empty() {
+            Some(format!("© {}", exif.artist))
+        } else {
+            None
+        }
+    }
+
+    /// Compose logo and text side by side, then apply opacity and rotation
+    fn stamp(
+        &self,
+        exif: &crate::exif_impl::SimplifiedExif,
+        short: f32,
+    ) -> Result<Option<image::RgbaImage>, image::ImageError> {
+        let height = (short * self.scale / 100.0).round().max(1.0) as u32;
+
+        let logo = match &self.logo_path {
+            Some(path) => Some(crate::theme::load_image_with_height(path, height)?),
+            None => None,
+        };
+
+        let text = self
+            .watermark_text(exif)
+            .filter(|_| self.show_text)
+            .map(|text| {
+                let text_h = if logo.is_some() {
+                    height as f32 * 0.6
+                } else {
+                    height as f32
+                };
+                let (scale, font) = (ab_glyph::PxScale::from(text_h), self.font.chain());
+                let (w, h) = text_dimensions(scale, &font, &text);
+                // Transparent around text, room for outline, shadow and plate
+                let extent = self.effects.extent(scale.y);
+                let mut image = image::RgbaImage::new(
+                    w.ceil() as u32 + extent * 2,
+                    h.ceil() as u32 + extent * 2,
+                );
+                let (x, y) = (extent as f32, extent as f32);
+                self.effects.draw_plate(&mut image, x, y, w, h, scale.y);
+                self.effects.draw_text(
+                    &mut image,
+                    &font,
+                    scale,
+                    x,
+                    y,
+                    crate::theme::color32_to_rgba(self.font_color),
+                    &text,
+                );
+                image
+            });
+
+        let gap = (height as f32 * 0.25).round() as u32;
+        let mut stamp = match (logo, text) {
+            (None, None) => return Ok(None),
+            (Some(x), None) | (None, Some(x)) => x,
+            (Some(logo), Some(text)) => {
+                let stamp_h = logo.height().max(text.height());
+                let mut stamp = image::RgbaImage::new(logo.width() + gap + text.width(), stamp_h);
+                image::imageops::overlay(
+                    &mut stamp,
+                    &logo,
+                    0,
+                    ((stamp_h - logo.height()) / 2) as i64,
+                );
+                image::imageops::overlay(
+                    &mut stamp,
+                    &text,
+                    (logo.width() + gap) as i64,
+                    ((stamp_h - text.height()) / 2) as i64,
+                );
+                stamp
+            }
+        };
+
+        let opacity = self.opacity.clamp(0.0, 100.0) / 100.0;
+        for px in stamp.pixels_mut() {
+            px[3] = (px[3] as f32 * opacity).round() as u8;
+        }
+
+        Ok(Some(effects::rotate_expand(&stamp, self.rotation)))
+    }
+}
+
+impl Theme for Watermark {
+    fn unique_name(&self) -> &'static str {
+        "watermark"
+    }
+
+    fn label(&self) -> std::borrow::Cow<'static, str> {
+        t!("theme.watermark")
+    }
+
+    fn apply(
+        &self,
+        pi: &crate::packed_image::PackedImage,
+        export_config: &crate::export_config::ExportConfig,
+        output_path: &std::path::Path,
+    ) -> Result<(), image::ImageError> {
+        let mut canvas = pi.developed(export_config)?.to_rgba8();
+        let (dyn_w, dyn_h) = canvas.dimensions();
+        let short = dyn_w.min(dyn_h) as f32;
+
+        match self.stamp(&pi.view_exif, short)? {
+            Some(stamp) if self.tiled => {
+                let (stamp_w, stamp_h) = (stamp.width() as i64, stamp.height() as i64);
+                let spacing = (stamp_w.max(stamp_h) as f32 * 0.5).round() as i64;
+                let (step_x, step_y) = (stamp_w + spacing, stamp_h + spacing);
+
+                for (row, y) in (-stamp_h / 2..dyn_h as i64)
+                    .step_by(step_y.max(1) as usize)
+                    .enumerate()
+                {
+                    let shift = if row % 2 == 0 { 0 } else { step_x / 2 };
+                    for x in (-shift..dyn_w as i64).step_by(step_x.max(1) as usize) {
+                        image::imageops::overlay(&mut canvas, &stamp, x, y);
+                    }
+                }
+            }
+            Some(stamp) => {
+                let (x, y) = self.anchor.position(
+                    dyn_w as f32,
+                    dyn_h as f32,
+                    stamp.width() as f32,
+                    stamp.height() as f32,
+                    short * self.margin / 100.0,
+                );
+                image::imageops::overlay(&mut canvas, &stamp, x.round() as i64, y.round() as i64);
+            }
+            None => {
+                log::warn!("Nothing to draw as watermark, no logo and no text");
+            }
+        }
+
+        export_config
+            .output_format
+            .save_image(&image::DynamicImage::ImageRgba8(canvas), output_path)
+    }
+
+    fn ui_config(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.watermark_config.logo"));
+
+                if ui
+                    .button(t!("theme.watermark_config.select_logo"))
+                    .clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter("image", &["png", "svg", "jpg", "jpeg", "webp"])
+                        .pick_file()
+                {
+                    self.logo_path = Some(path);
+                }
+
+                if self.logo_path.is_some()
+                    && ui.button(t!("theme.watermark_config.clear_logo")).clicked()
+                {
+                    self.logo_path = None;
+                }
+
+                if let Some(path) = &self.logo_path {
+                    ui.label(path.display().to_string());
+                }
+            });
+
+            ui.checkbox(&mut self.show_text, t!("theme.watermark_config.show_text"));
+            ui.add_enabled(
+                self.show_text,
+                egui::TextEdit::singleline(&mut self.text)
+                    .hint_text(t!("theme.watermark_config.text_hint")),
+            );
+            ui.add_enabled_ui(self.show_text, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme.font"));
+                    self.font.update_ui(ui, "watermark_font");
+                });
+            });
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.tiled, t!("theme.watermark_config.tiled"));
+                ui.add_enabled_ui(!self.tiled, |ui| {
+                    self.anchor.update_ui(ui, "watermark_anchor");
+                });
+            });
+
+            ui.add_enabled(
+                !self.tiled,
+                egui::Slider::new(&mut self.margin, 0.0..=20.0)
+                    .suffix("%")
+                    .text(t!("theme.watermark_config.margin")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.scale, 1.0..=50.0)
+                    .suffix("%")
+                    .text(t!("theme.watermark_config.scale")),
+            )
+            .on_hover_text(t!("theme.watermark_config.scale_description"));
+            ui.add(
+                egui::Slider::new(&mut self.opacity, 0.0..=100.0)
+                    .suffix("%")
+                    .text(t!("theme.watermark_config.opacity")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.rotation, -180.0..=180.0)
+                    .suffix("°")
+                    .text(t!("theme.watermark_config.rotation")),
+            );
+
+            ui.horizontal(|ui| {
+                ui.label(t!("theme.font_color"));
+                egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut self.font_color,
+                    egui::color_picker::Alpha::OnlyBlend,
+                );
+            });
+            ui.add_enabled_ui(self.show_text, |ui| {
+                self.effects.update_ui(ui, "watermark_text_effects");
+            });
+        });
+    }
+}